@@ -0,0 +1,55 @@
+// src/all_pay.rs
+
+// All-pay auction: every bidder pays their bid and the highest bid wins.
+//
+// The winner is found exactly as in the sealed-bid auction, and the total
+// revenue is the same encrypted sum the average is built from. Each bidder
+// registers their own tfhe public key and gets their payment as a ciphertext
+// under it, so the delivered result can only be opened by that bidder. All
+// bids are encrypted under the auction's single client key, though, so the
+// re-encryption decrypts each payment on the auctioneer's side: the auctioneer
+// learns every payment, and the leakage log says so (without the values).
+
+use std::collections::HashMap;
+use tfhe::{FheUint16, FheUint32, PublicKey};
+use crate::auction::{Auction, AuctionError};
+use crate::disclosure::Disclosure;
+
+/// Outcome of an all-pay auction.
+pub struct AllPayOutcome {
+    pub winners: Vec<String>,                 // Highest bidder(s), as in compute_max_encrypted_bid
+    pub revenue: FheUint32,                   // Encrypted total of all payments
+    pub payments: HashMap<String, FheUint16>, // Payment per bidder, encrypted under that bidder's own key
+}
+
+impl<'a> Auction<'a> {
+    /// Register the public key a bidder's private results are encrypted under
+    /// (replacing an earlier one).
+    pub fn register_bidder_key(&mut self, user_id: &str, key: PublicKey) {
+        self.set_bidder_key(user_id, key);
+    }
+
+    /// Settle the auction as all-pay, with each payment re-encrypted under its
+    /// bidder's key. Every bidder must have registered a key; this is checked,
+    /// in bidder order, before anything is decrypted.
+    pub fn compute_all_pay(&self) -> Result<AllPayOutcome, AuctionError> {
+        let mut bids: Vec<(&String, &FheUint16)> = self.bids_by_user().collect();
+        bids.sort_by(|a, b| a.0.cmp(b.0));
+        if let Some((user, _)) = bids.iter().find(|(user, _)| self.bidder_key(user).is_none()) {
+            return Err(AuctionError::NotRegistered(user.to_string()));
+        }
+
+        let (_, winners) = self.compute_max_encrypted_bid()?;
+        let mut payments = HashMap::new();
+        for (user, enc_bid) in bids {
+            let item = format!("{}'s all-pay payment", user);
+            let payment = self.reencrypt_for(user, Disclosure::ClearingPrice, &item, enc_bid)?;
+            payments.insert(user.clone(), payment);
+        }
+        Ok(AllPayOutcome {
+            winners,
+            revenue: self.compute_sum_encrypted(),
+            payments,
+        })
+    }
+}
@@ -1,12 +1,211 @@
-use std::collections::HashMap;
-use tfhe::{FheUint16, FheUint32};
+use std::cell::{Ref, RefCell};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use tfhe::{FheBool, FheUint16, FheUint32};
 use tfhe::prelude::*;  // Bring traits like .eq, .max into scope
-use tfhe::ClientKey;
+use tfhe::{ClientKey, PublicKey};
+use crate::clock::{Clock, SystemClock};
+use crate::consortium::Consortium;
+use crate::deadline::{DeadlineScheduler, SoftClose};
+use crate::disclosure::{Disclosure, DisclosurePolicy};
+use crate::leakage::{LeakageLog, AUCTIONEER};
+use crate::privacy::PrivacyBudget;
+use crate::sorting::{self, EncryptedEntry, EncryptedRanking};
+
+/// Index used for placeholder entries that do not belong to any bidder.
+pub(crate) const NO_BIDDER: u16 = u16::MAX;
+
+/// How an existing bid may be changed by a later submission from the same user.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmendmentPolicy {
+    /// A user's first bid is final; later submissions are rejected.
+    Forbid,
+    /// Any later submission replaces the previous bid.
+    AllowAny,
+    /// A later submission only takes effect if it is higher than the current bid.
+    /// The comparison is done homomorphically, so a lower amendment is ignored
+    /// without anyone learning that it was lower.
+    IncreaseOnly,
+}
+
+/// Which bid sets the uniform price in a multi-unit auction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UniformPriceRule {
+    /// The lowest winning bid (k-th highest).
+    KthHighest,
+    /// The highest losing bid ((k+1)-th highest).
+    KPlusOneHighest,
+}
+
+/// How an encrypted division by a public count is rounded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round down.
+    Floor,
+    /// Round to nearest, halves rounded up.
+    Nearest,
+    /// Round up.
+    Ceiling,
+}
+
+/// An encrypted average together with the values it was computed from.
+pub struct EncryptedAverage {
+    pub sum: FheUint32,       // Encrypted sum of the bids that were averaged
+    pub count: u32,           // Number of bids that were averaged (public)
+    pub average: FheUint16,   // Encrypted sum / count, rounded as requested
+}
+
+/// Homomorphically divide an encrypted sum by a public count with the given rounding.
+fn divide_rounded(sum: &FheUint32, count: u32, rounding: RoundingMode) -> FheUint16 {
+    // Rounding is done by adding a public offset before the (flooring) division.
+    let offset = match rounding {
+        RoundingMode::Floor => 0,
+        RoundingMode::Nearest => count / 2,
+        RoundingMode::Ceiling => count - 1,
+    };
+    let quotient = (sum + offset) / count;
+    // Cast the 32‑bit ciphertext back to 16‑bit
+    FheUint16::cast_from(quotient)
+}
+
+/// A buy-it-now price that closes the auction as soon as a bid meets it.
+pub enum BuyItNow {
+    /// A price announced to everyone.
+    Public(u16),
+    /// A price kept secret by the seller.
+    Encrypted(FheUint16),
+}
+
+/// A bid as it stood after one submission, with the time it was submitted.
+/// Keeping the submitted ciphertext lets an auditor re-check the encrypted
+/// amendment decision that produced `value`.
+#[derive(Clone)]
+pub struct TimedBid {
+    pub at: u64,               // Seconds since the auction opened
+    pub submitted: FheUint16,  // Bid as submitted (may have been ignored as an amendment)
+    pub value: FheUint16,      // Bid in effect after this submission
+}
+
+/// Lifecycle stages of an auction, in the order they occur.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AuctionPhase {
+    /// Bids are being collected.
+    Bidding,
+    /// Tied top bidders are rebidding.
+    TieBreak,
+    /// The auction is over and results are being computed.
+    Closed,
+}
+
+/// A recorded withdrawal of a bid.
+#[derive(Clone, Debug)]
+pub struct Withdrawal {
+    pub user_id: String,
+    pub phase: AuctionPhase,      // Lifecycle stage the withdrawal happened in
+    pub at: u64,                  // Seconds since the auction opened
+    pub penalty: Option<u16>,     // Penalty charged to the user, if any
+}
+
+/// An entry in a bidder's ledger.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LedgerEntry {
+    /// Penalty charged for withdrawing a bid during the given phase.
+    WithdrawalPenalty { amount: u16, phase: AuctionPhase },
+}
+
+/// Errors returned by operations that can be refused by the auction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuctionError {
+    /// The user already has a bid and the amendment policy forbids changes.
+    AmendmentForbidden(String),
+    /// The user has no bid to act on.
+    NoBid(String),
+    /// Withdrawals are no longer accepted in the current phase.
+    WithdrawalClosed(AuctionPhase),
+    /// The disclosure policy does not allow this output to be decrypted.
+    NotDisclosed(Disclosure),
+    /// A noisy disclosure would exceed the privacy budget (or none is configured).
+    PrivacyBudgetExhausted,
+    /// A bundle refers to an item that is not being auctioned.
+    UnknownItem(String),
+    /// The auction is closed and no longer accepts bids.
+    AuctionClosed,
+    /// The user is not in the registry of bidders (or voters).
+    NotRegistered(String),
+    /// The bid is timestamped before the user's previous bid.
+    OutOfOrder(String),
+    /// The bid is timestamped after the current time.
+    FutureTimestamp(String),
+    /// The bidder has not won, so a winner-only output is refused.
+    NotWinner(String),
+    /// Calibrated noise for a disclosure would need this many encrypted
+    /// trials per sample, more than are supported.
+    NoiseTooLarge(u32),
+}
+
+impl fmt::Display for AuctionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuctionError::AmendmentForbidden(user) => {
+                write!(f, "User '{}' has already bid and amendments are not allowed", user)
+            }
+            AuctionError::NoBid(user) => write!(f, "User '{}' has no bid", user),
+            AuctionError::WithdrawalClosed(phase) => {
+                write!(f, "Withdrawals are not allowed during the {:?} phase", phase)
+            }
+            AuctionError::NotDisclosed(what) => {
+                write!(f, "The disclosure policy does not allow revealing the {}", what)
+            }
+            AuctionError::PrivacyBudgetExhausted => {
+                write!(f, "The privacy budget does not allow another noisy disclosure")
+            }
+            AuctionError::UnknownItem(item) => write!(f, "Item '{}' is not part of this auction", item),
+            AuctionError::AuctionClosed => write!(f, "The auction is closed"),
+            AuctionError::NotRegistered(user) => write!(f, "User '{}' is not registered", user),
+            AuctionError::OutOfOrder(user) => {
+                write!(f, "Bid from user '{}' is timestamped before their previous bid", user)
+            }
+            AuctionError::FutureTimestamp(user) => {
+                write!(f, "Bid from user '{}' is timestamped after the current time", user)
+            }
+            AuctionError::NotWinner(user) => write!(f, "User '{}' has not won the auction", user),
+            AuctionError::NoiseTooLarge(trials) => write!(
+                f,
+                "Calibrated noise would need {} encrypted trials per sample; use a larger epsilon or a tighter bound",
+                trials
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AuctionError {}
 
 pub struct Auction<'a> {
     client_key: &'a ClientKey,                 // Reference to the client's secret key (for encryption/decryption)
     min_bid: u16,                              // Minimum bid threshold
     bids: HashMap<String, FheUint16>,          // Encrypted bids mapped by user ID
+    amendment_policy: AmendmentPolicy,         // How repeated bids from the same user are handled
+    history: HashMap<String, Vec<TimedBid>>,   // Every submission per user, oldest first (audit trail)
+    clock: Box<dyn Clock>,                     // Time source for timestamps and deadlines
+    opened_at: u64,                            // Clock time when the auction opened (timestamps count from here)
+    deadline: Option<DeadlineScheduler>,       // Soft-close deadline, if the auction is timed
+    phase: AuctionPhase,                       // Current lifecycle stage
+    withdrawal_cutoff: AuctionPhase,           // First phase in which withdrawals are refused
+    withdrawal_penalty: Option<u16>,           // Penalty recorded for each withdrawal, if any
+    withdrawals: Vec<Withdrawal>,              // Every withdrawal, in order
+    ledger: HashMap<String, Vec<LedgerEntry>>, // Penalties and other charges per user
+    disclosure_policy: DisclosurePolicy,       // Outputs that may be decrypted
+    privacy_budget: Option<PrivacyBudget>,     // Epsilon budget for noisy disclosures, if enabled
+    leakage: RefCell<LeakageLog>,              // Every decryption performed, and who learned it
+    buy_it_now: Option<BuyItNow>,              // Buy-it-now price, if any
+    bin_reached: Option<FheBool>,              // Encrypted OR of every "bid >= buy-it-now" flag so far
+    bin_met: bool,                             // Decrypted value of bin_reached
+    bin_trigger: Option<FheUint16>,            // Encrypted submission number of the first bid to meet it
+    submissions: Vec<String>,                  // User of each accepted submission, in order
+    vectors: HashMap<String, Vec<FheUint16>>,  // Encrypted value vectors per user (tender attributes, ballots)
+    registry: BTreeSet<String>,                // Registered bidders (or voters)
+    consortia: HashMap<String, Consortium>,    // Joint bidders, by consortium name
+    bidder_keys: HashMap<String, PublicKey>,   // Bidders' own keys, for results only they may decrypt
 }
 
 impl<'a> Auction<'a> {
@@ -16,47 +215,531 @@ impl<'a> Auction<'a> {
             client_key,
             min_bid,
             bids: HashMap::new(),
+            amendment_policy: AmendmentPolicy::AllowAny,
+            history: HashMap::new(),
+            clock: Box::new(SystemClock),
+            opened_at: SystemClock.now(),
+            deadline: None,
+            phase: AuctionPhase::Bidding,
+            withdrawal_cutoff: AuctionPhase::Closed,
+            withdrawal_penalty: None,
+            withdrawals: Vec::new(),
+            ledger: HashMap::new(),
+            disclosure_policy: DisclosurePolicy::default(),
+            privacy_budget: None,
+            leakage: RefCell::new(LeakageLog::default()),
+            buy_it_now: None,
+            bin_reached: None,
+            bin_met: false,
+            bin_trigger: None,
+            submissions: Vec::new(),
+            vectors: HashMap::new(),
+            registry: BTreeSet::new(),
+            consortia: HashMap::new(),
+            bidder_keys: HashMap::new(),
         }
     }
 
-    // Get a reference to the client key (needed for decryption in main).
-    pub fn client_key(&self) -> &'a ClientKey {
+    /// Set how later bids from a user who has already bid are handled.
+    pub fn with_amendment_policy(mut self, policy: AmendmentPolicy) -> Self {
+        self.amendment_policy = policy;
+        self
+    }
+
+    /// Return the amendment policy in force.
+    pub fn amendment_policy(&self) -> AmendmentPolicy {
+        self.amendment_policy
+    }
+
+    /// Refuse withdrawals from the given phase onwards (default: `Closed`).
+    pub fn with_withdrawal_cutoff(mut self, cutoff: AuctionPhase) -> Self {
+        self.withdrawal_cutoff = cutoff;
+        self
+    }
+
+    /// Record a penalty of `amount` in the ledger of every user who withdraws.
+    pub fn with_withdrawal_penalty(mut self, amount: u16) -> Self {
+        self.withdrawal_penalty = Some(amount);
+        self
+    }
+
+    /// Restrict which outputs may be decrypted (default: everything).
+    pub fn with_disclosure_policy(mut self, policy: DisclosurePolicy) -> Self {
+        self.disclosure_policy = policy;
+        self
+    }
+
+    /// Return the disclosure policy in force.
+    pub fn disclosure_policy(&self) -> &DisclosurePolicy {
+        &self.disclosure_policy
+    }
+
+    /// Read the time from `clock` instead of the system clock. The auction is
+    /// treated as opening at the clock's current time.
+    pub fn with_clock(mut self, clock: Box<dyn Clock>) -> Self {
+        self.opened_at = clock.now();
+        self.clock = clock;
+        self
+    }
+
+    /// Make the auction timed, closing with a soft (extendable) deadline.
+    pub fn with_soft_close(mut self, config: SoftClose) -> Self {
+        self.deadline = Some(DeadlineScheduler::new(config));
+        self
+    }
+
+    /// Return the seconds elapsed since the auction opened.
+    pub fn elapsed_secs(&self) -> u64 {
+        self.clock.now().saturating_sub(self.opened_at)
+    }
+
+    /// Return the current deadline in seconds since opening, if the auction is timed.
+    pub fn deadline(&self) -> Option<u64> {
+        self.deadline.as_ref().map(DeadlineScheduler::deadline)
+    }
+
+    /// Close the auction if its deadline has passed. Returns true if it is closed.
+    pub fn close_if_expired(&mut self) -> bool {
+        let at = self.elapsed_secs();
+        if self.deadline.as_ref().is_some_and(|d| !d.is_open(at)) {
+            self.phase = AuctionPhase::Closed;
+        }
+        self.phase == AuctionPhase::Closed
+    }
+
+    /// Close the auction as soon as a bid meets the buy-it-now price.
+    pub fn with_buy_it_now(mut self, price: BuyItNow) -> Self {
+        self.buy_it_now = Some(price);
+        self
+    }
+
+    /// Enable noisy (differentially private) disclosures with a total epsilon budget.
+    pub fn with_privacy_budget(mut self, epsilon: f64) -> Self {
+        self.privacy_budget = Some(PrivacyBudget::new(epsilon));
+        self
+    }
+
+    /// Return the privacy budget, if noisy disclosures are enabled.
+    pub fn privacy_budget(&self) -> Option<&PrivacyBudget> {
+        self.privacy_budget.as_ref()
+    }
+
+    pub(crate) fn privacy_budget_mut(&mut self) -> Option<&mut PrivacyBudget> {
+        self.privacy_budget.as_mut()
+    }
+
+    /// Describe the auction's public terms, including what it will reveal.
+    pub fn public_terms(&self) -> String {
+        let amendments = match self.amendment_policy {
+            AmendmentPolicy::Forbid => "not allowed",
+            AmendmentPolicy::AllowAny => "any change allowed",
+            AmendmentPolicy::IncreaseOnly => "increases only",
+        };
+        let penalty = self
+            .withdrawal_penalty
+            .map_or("none".to_string(), |p| p.to_string());
+        let mut terms = format!(
+            "Minimum bid: above {}\nBid amendments: {}\nWithdrawals: until the {:?} phase (penalty: {})\nDisclosed outputs: {}",
+            self.min_bid, amendments, self.withdrawal_cutoff, penalty, self.disclosure_policy
+        );
+        match &self.buy_it_now {
+            Some(BuyItNow::Public(price)) => terms.push_str(&format!("\nBuy-it-now price: {}", price)),
+            Some(BuyItNow::Encrypted(_)) => terms.push_str("\nBuy-it-now price: sealed"),
+            None => {}
+        }
+        if let Some(deadline) = &self.deadline {
+            terms.push_str(&format!(
+                "\nDeadline: {}s after opening, extended by late bids",
+                deadline.deadline()
+            ));
+        }
+        if let Some(budget) = &self.privacy_budget {
+            terms.push_str(&format!(
+                "\nDifferential privacy: noisy aggregates, epsilon budget {}",
+                budget.total()
+            ));
+        }
+        terms
+    }
+
+    /// Decrypt an output on the auctioneer's behalf, if the disclosure policy allows it.
+    /// Only the auction's own computations pass ciphertexts here; callers
+    /// outside the crate go through the output-specific `reveal_*` methods.
+    pub(crate) fn reveal<T, C>(&self, what: Disclosure, ciphertext: &C) -> Result<T, AuctionError>
+    where
+        C: FheDecrypt<T>,
+        T: fmt::Debug,
+    {
+        self.reveal_to(AUCTIONEER, what, &what.to_string(), ciphertext)
+    }
+
+    /// Decrypt an output for `party`, if the disclosure policy allows it, and
+    /// record in the leakage log that `party` learned `item`.
+    pub(crate) fn reveal_to<T, C>(
+        &self,
+        party: &str,
+        what: Disclosure,
+        item: &str,
+        ciphertext: &C,
+    ) -> Result<T, AuctionError>
+    where
+        C: FheDecrypt<T>,
+        T: fmt::Debug,
+    {
+        if !self.disclosure_policy.allows(what) {
+            return Err(AuctionError::NotDisclosed(what));
+        }
+        let value = ciphertext.decrypt(self.client_key);
+        self.record_disclosure(party, what, item, &format!("{:?}", value));
+        Ok(value)
+    }
+
+    /// Re-encrypt an output under the public key `user_id` registered, if the
+    /// disclosure policy allows it. This is a decrypt-then-encrypt with the
+    /// auction's client key, so the auctioneer learns the value on the way; the
+    /// log records that against the auctioneer, without the value.
+    pub(crate) fn reencrypt_for(
+        &self,
+        user_id: &str,
+        what: Disclosure,
+        item: &str,
+        ciphertext: &FheUint16,
+    ) -> Result<FheUint16, AuctionError> {
+        if !self.disclosure_policy.allows(what) {
+            return Err(AuctionError::NotDisclosed(what));
+        }
+        let key = self
+            .bidder_key(user_id)
+            .ok_or_else(|| AuctionError::NotRegistered(user_id.to_string()))?;
+        let value: u16 = ciphertext.decrypt(self.client_key);
+        self.record_disclosure(AUCTIONEER, what, item, &format!("withheld (re-encrypted for {})", user_id));
+        Ok(FheUint16::encrypt(value, key))
+    }
+
+    /// Record that `party` learned an already-decrypted value (for example when
+    /// the CLI announces a result).
+    pub fn record_disclosure(&self, party: &str, what: Disclosure, item: &str, value: &str) {
+        self.leakage.borrow_mut().record(party, what, item, value.to_string());
+    }
+
+    /// Return the leakage log of this run, for the settlement report.
+    pub fn leakage_log(&self) -> Ref<'_, LeakageLog> {
+        self.leakage.borrow()
+    }
+
+    /// Reveal the number of bids received, if the disclosure policy allows it.
+    pub fn reveal_bid_count(&self) -> Result<usize, AuctionError> {
+        if !self.disclosure_policy.allows(Disclosure::BidCount) {
+            return Err(AuctionError::NotDisclosed(Disclosure::BidCount));
+        }
+        let count = self.bids.len();
+        self.record_disclosure(AUCTIONEER, Disclosure::BidCount, "number of bids", &count.to_string());
+        Ok(count)
+    }
+
+    /// Reveal the highest bid to `party`, if the disclosure policy allows the
+    /// clearing price to be disclosed.
+    pub fn reveal_highest_bid(&self, party: &str) -> Result<u16, AuctionError> {
+        if !self.disclosure_policy.allows(Disclosure::ClearingPrice) {
+            return Err(AuctionError::NotDisclosed(Disclosure::ClearingPrice));
+        }
+        self.reveal_to(party, Disclosure::ClearingPrice, "highest bid", &self.encrypted_max())
+    }
+
+    /// Reveal the average of all bids to `party`, rounded as requested, if the
+    /// disclosure policy allows it.
+    pub fn reveal_average(&self, party: &str, rounding: RoundingMode) -> Result<u16, AuctionError> {
+        if !self.disclosure_policy.allows(Disclosure::Average) {
+            return Err(AuctionError::NotDisclosed(Disclosure::Average));
+        }
+        let average = self.compute_average_with(rounding).average;
+        self.reveal_to(party, Disclosure::Average, "average bid", &average)
+    }
+
+    /// Reveal the bidder order of a ranking (bid values stay encrypted),
+    /// if the disclosure policy allows it.
+    pub fn reveal_ranks(&self, ranking: &EncryptedRanking) -> Result<Vec<String>, AuctionError> {
+        if !self.disclosure_policy.allows(Disclosure::Ranks) {
+            return Err(AuctionError::NotDisclosed(Disclosure::Ranks));
+        }
+        let ranks = ranking.decrypt_ranks(self.client_key);
+        self.record_disclosure(AUCTIONEER, Disclosure::Ranks, "bidder order", &ranks.join(" > "));
+        Ok(ranks)
+    }
+
+    /// Return the current lifecycle phase.
+    pub fn phase(&self) -> AuctionPhase {
+        self.phase
+    }
+
+    /// Move the auction to another lifecycle phase.
+    pub fn set_phase(&mut self, phase: AuctionPhase) {
+        self.phase = phase;
+    }
+
+    /// Return the client key, for encrypting inside the crate.
+    pub(crate) fn client_key(&self) -> &'a ClientKey {
         self.client_key
     }
 
-    // Encrypt a user's bid and store it (or update if the user already has a bid).
+    /// Return the minimum bid threshold.
+    pub fn min_bid(&self) -> u16 {
+        self.min_bid
+    }
+
+    // Encrypt a user's bid and store it (or amend it if the user already has a bid,
+    // according to the amendment policy).
     // Assumes bid_value is validated to be > min_bid.
-    pub fn add_bid(&mut self, user_id: &str, bid_value: u16) {
+    pub fn add_bid(&mut self, user_id: &str, bid_value: u16) -> Result<(), AuctionError> {
+        let at = self.elapsed_secs();
+        self.add_bid_at(user_id, bid_value, at)
+    }
+
+    /// Like `add_bid`, with an explicit submission time in seconds since the
+    /// auction opened (for replaying recorded bids). A user's bids must be
+    /// replayed in time order, so their history stays sorted by time, and
+    /// `at` may not lie in the future. The deadline is always checked against
+    /// the clock, whatever `at` says.
+    pub fn add_bid_at(&mut self, user_id: &str, bid_value: u16, at: u64) -> Result<(), AuctionError> {
         // Encrypt the bid using the ClientKey. This produces an FHE ciphertext.
         let enc_bid = FheUint16::encrypt(bid_value, self.client_key);
-        // Insert or update the bid in the HashMap
+        self.add_encrypted_bid_at(user_id, enc_bid, at)
+    }
+
+    /// Store an already-encrypted bid through the same checks as `add_bid_at`
+    /// (deadline, timestamps, amendment policy, buy-it-now and history).
+    pub(crate) fn add_encrypted_bid_at(
+        &mut self,
+        user_id: &str,
+        enc_bid: FheUint16,
+        at: u64,
+    ) -> Result<(), AuctionError> {
+        if self.close_if_expired() {
+            return Err(AuctionError::AuctionClosed);
+        }
+        if at > self.elapsed_secs() {
+            return Err(AuctionError::FutureTimestamp(user_id.to_string()));
+        }
+        if self.bid_history(user_id).last().is_some_and(|last| at < last.at) {
+            return Err(AuctionError::OutOfOrder(user_id.to_string()));
+        }
+
+        // A withdrawn bid still counts as the previous bid, so withdrawing and
+        // bidding again cannot get around the amendment policy.
+        let previous = self
+            .bids
+            .get(user_id)
+            .or_else(|| self.bid_history(user_id).last().map(|last| &last.value));
+        let effective = match (previous, self.amendment_policy) {
+            (None, _) | (Some(_), AmendmentPolicy::AllowAny) => enc_bid.clone(),
+            (Some(_), AmendmentPolicy::Forbid) => {
+                return Err(AuctionError::AmendmentForbidden(user_id.to_string()));
+            }
+            (Some(previous), AmendmentPolicy::IncreaseOnly) => {
+                // Keep the new bid only if new > old; the result stays encrypted.
+                let is_higher = enc_bid.gt(previous);
+                is_higher.select(&enc_bid, previous)
+            }
+        };
+
+        // With a buy-it-now price, only the running OR of "bid >= price" is
+        // decrypted; the first bid to meet it closes the auction.
+        let mut closes_auction = false;
+        if let Some(bin) = &self.buy_it_now {
+            let meets = match bin {
+                BuyItNow::Public(price) => effective.ge(*price),
+                BuyItNow::Encrypted(enc_price) => effective.ge(enc_price),
+            };
+            let submission = FheUint16::encrypt(self.submissions.len() as u16, self.client_key);
+            let (reached, trigger) = match (&self.bin_reached, &self.bin_trigger) {
+                (Some(reached), Some(trigger)) => {
+                    let first = &meets & &!reached;
+                    (reached | &meets, first.select(&submission, trigger))
+                }
+                _ => (meets, submission),
+            };
+            closes_auction = self.reveal(Disclosure::BuyItNow, &reached)?;
+            self.bin_reached = Some(reached);
+            self.bin_trigger = Some(trigger);
+        }
+
+        // Insert or update the bid in the HashMap and append it to the audit trail
+        self.submissions.push(user_id.to_string());
+        if closes_auction {
+            self.bin_met = true;
+            self.phase = AuctionPhase::Closed;
+        }
+        if let Some(deadline) = &mut self.deadline {
+            // A valid bid close to the deadline extends it.
+            deadline.register_bid(at);
+        }
+        self.history
+            .entry(user_id.to_string())
+            .or_default()
+            .push(TimedBid { at, submitted: enc_bid, value: effective.clone() });
+        self.bids.insert(user_id.to_string(), effective);
+        Ok(())
+    }
+
+    /// Identify the bidder whose bid met the buy-it-now price, by decrypting
+    /// only the encrypted submission number of the triggering bid.
+    /// Returns None if no bid has met it.
+    pub fn buy_it_now_buyer(&self) -> Result<Option<String>, AuctionError> {
+        let Some(trigger) = self.bin_trigger.as_ref().filter(|_| self.bin_met) else {
+            return Ok(None);
+        };
+        let submission: u16 =
+            self.reveal_to(AUCTIONEER, Disclosure::WinnerIdentity, "buy-it-now submission", trigger)?;
+        Ok(self.submissions.get(submission as usize).cloned())
+    }
+
+    /// Return the encrypted amendment history of a user, sorted by submission time.
+    /// Each entry holds the submitted bid and the bid in effect after it.
+    pub fn bid_history(&self, user_id: &str) -> &[TimedBid] {
+        self.history.get(user_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Withdraw a user's bid, recording who withdrew, when, and any penalty.
+    /// Refused once the auction has reached the withdrawal cutoff phase. A
+    /// later bid from the same user is still judged against the withdrawn one
+    /// under the amendment policy.
+    pub fn withdraw_bid(&mut self, user_id: &str) -> Result<(), AuctionError> {
+        if self.phase >= self.withdrawal_cutoff {
+            return Err(AuctionError::WithdrawalClosed(self.phase));
+        }
+        if self.bids.remove(user_id).is_none() {
+            return Err(AuctionError::NoBid(user_id.to_string()));
+        }
+
+        if let Some(amount) = self.withdrawal_penalty {
+            self.ledger
+                .entry(user_id.to_string())
+                .or_default()
+                .push(LedgerEntry::WithdrawalPenalty { amount, phase: self.phase });
+        }
+        self.withdrawals.push(Withdrawal {
+            user_id: user_id.to_string(),
+            phase: self.phase,
+            at: self.elapsed_secs(),
+            penalty: self.withdrawal_penalty,
+        });
+        Ok(())
+    }
+
+    /// Return every withdrawal recorded so far, in order.
+    pub fn withdrawals(&self) -> &[Withdrawal] {
+        &self.withdrawals
+    }
+
+    /// Return the ledger entries of a user.
+    pub fn ledger(&self, user_id: &str) -> &[LedgerEntry] {
+        self.ledger.get(user_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Export the audit trail as text: one line per bidder with the number of
+    /// submissions, followed by one line per withdrawal. No bid values are included.
+    pub fn audit_export(&self) -> String {
+        let mut users: Vec<&String> = self.history.keys().collect();
+        users.sort();
+
+        let mut out = String::new();
+        for user in users {
+            out.push_str(&format!(
+                "bid user={} submissions={} active={}\n",
+                user,
+                self.history[user].len(),
+                self.bids.contains_key(user)
+            ));
+        }
+        for w in &self.withdrawals {
+            out.push_str(&format!(
+                "withdrawal user={} phase={:?} at={} penalty={}\n",
+                w.user_id,
+                w.phase,
+                w.at,
+                w.penalty.map_or("none".to_string(), |p| p.to_string())
+            ));
+        }
+        out
+    }
+
+    /// Overwrite a stored bid with an already-encrypted value, bypassing the
+    /// amendment policy (used to apply oblivious adjustments such as budget caps).
+    pub(crate) fn replace_encrypted_bid(&mut self, user_id: &str, enc_bid: FheUint16) {
         self.bids.insert(user_id.to_string(), enc_bid);
     }
 
-    // Remove a user's bid
-    pub fn remove_bid(&mut self, user_id: &str) {
-        self.bids.remove(user_id);
+    /// Return the encrypted value vectors (tender attributes, ballots), keyed by user.
+    pub(crate) fn encrypted_vectors(&self) -> &HashMap<String, Vec<FheUint16>> {
+        &self.vectors
     }
 
-    /// Return the number of bids currently stored.
-    pub fn count_bids(&self) -> usize {
-        self.bids.len()
+    /// Store a user's encrypted value vector (replacing an earlier one).
+    pub(crate) fn set_encrypted_vector(&mut self, user_id: &str, values: Vec<FheUint16>) {
+        self.vectors.insert(user_id.to_string(), values);
     }
 
-    /// Compute the encrypted maximum bid among all stored bids.
-    pub fn compute_max_encrypted_bid(&self) -> (FheUint16, Vec<String>) {
+    /// Add a user to the registry. Returns false if they were already registered.
+    pub fn register_bidder(&mut self, user_id: &str) -> bool {
+        self.registry.insert(user_id.to_string())
+    }
+
+    /// Return true if the user is registered.
+    pub fn is_registered(&self, user_id: &str) -> bool {
+        self.registry.contains(user_id)
+    }
+
+    /// Return the registered users, sorted by ID.
+    pub fn registered_bidders(&self) -> impl Iterator<Item = &String> {
+        self.registry.iter()
+    }
+
+    /// Return the consortia, keyed by name.
+    pub(crate) fn consortia(&self) -> &HashMap<String, Consortium> {
+        &self.consortia
+    }
+
+    /// Return the consortia for updating, keyed by name.
+    pub(crate) fn consortia_mut(&mut self) -> &mut HashMap<String, Consortium> {
+        &mut self.consortia
+    }
+
+    /// Return a bidder's registered public key.
+    pub(crate) fn bidder_key(&self, user_id: &str) -> Option<&PublicKey> {
+        self.bidder_keys.get(user_id)
+    }
+
+    /// Store a bidder's public key (replacing an earlier one).
+    pub(crate) fn set_bidder_key(&mut self, user_id: &str, key: PublicKey) {
+        self.bidder_keys.insert(user_id.to_string(), key);
+    }
+
+    /// Compute the encrypted maximum of all stored bids.
+    fn encrypted_max(&self) -> FheUint16 {
         assert!(!self.bids.is_empty(), "No bids to compute max from");
 
         // Iterates through the bids and use the .max() operation pairwise.
-        let mut iter = self.bids.iter();
-        let (_, first_enc) = iter.next().unwrap();
+        let mut iter = self.bids.values();
         // Start with the first bid as current max
-        let mut current_max = first_enc.clone();
+        let mut current_max = iter.next().unwrap().clone();
         // Iterate through remaining bids and update the max ciphertext
-        for (_, enc_bid) in iter {
+        for enc_bid in iter {
             // homomorphic max operation
             current_max = current_max.max(enc_bid);
         }
+        current_max
+    }
+
+    /// Return the number of bids currently stored.
+    pub fn count_bids(&self) -> usize {
+        self.bids.len()
+    }
+
+    /// Compute the encrypted maximum bid among all stored bids, together with
+    /// the top bidder(s). Fails if the disclosure policy does not allow
+    /// revealing the winner identity.
+    pub fn compute_max_encrypted_bid(&self) -> Result<(FheUint16, Vec<String>), AuctionError> {
+        let current_max = self.encrypted_max();
 
         // Determine which user(s) have this max value
         let mut top_users: Vec<String> = Vec::new();
@@ -64,31 +747,150 @@ impl<'a> Auction<'a> {
             // Homomorphic comparison: check if enc_bid equals current_max
             let is_equal = enc_bid.eq(&current_max);
             // Decrypt the comparison result to a bool (true if equal)
-            if is_equal.decrypt(self.client_key) {
+            let item = format!("{} holds the highest bid", user);
+            if self.reveal_to(AUCTIONEER, Disclosure::WinnerIdentity, &item, &is_equal)? {
                 top_users.push(user.clone());
             }
         }
-        (current_max, top_users)
+        Ok((current_max, top_users))
+    }
+
+    /// Return the bidder IDs in a fixed (sorted) order together with their bids,
+    /// each paired with the encrypted position of its bidder in that order.
+    pub(crate) fn indexed_bids(&self) -> (Vec<String>, Vec<EncryptedEntry>) {
+        let mut users: Vec<String> = self.bids.keys().cloned().collect();
+        users.sort();
+        let entries = users
+            .iter()
+            .enumerate()
+            .map(|(i, user)| EncryptedEntry {
+                value: self.bids[user].clone(),
+                index: FheUint16::encrypt(i as u16, self.client_key),
+            })
+            .collect();
+        (users, entries)
+    }
+
+    /// Entry standing in for a missing bid: worth the minimum bid, owned by nobody.
+    pub(crate) fn reserve_entry(&self) -> EncryptedEntry {
+        EncryptedEntry {
+            value: FheUint16::encrypt(self.min_bid, self.client_key),
+            index: FheUint16::encrypt(NO_BIDDER, self.client_key),
+        }
+    }
+
+    /// Decrypt the bidder index of an entry into a user ID. Returns None for
+    /// placeholders, and fails if the disclosure policy does not allow revealing winners.
+    pub(crate) fn reveal_bidder(
+        &self,
+        users: &[String],
+        entry: &EncryptedEntry,
+    ) -> Result<Option<String>, AuctionError> {
+        let index: u16 = self.reveal_to(AUCTIONEER, Disclosure::WinnerIdentity, "bidder index", &entry.index)?;
+        Ok(users.get(index as usize).cloned())
+    }
+
+    /// Multi-unit uniform-price auction: select the `k` highest bids obliviously
+    /// with a partial sorting network and return the encrypted clearing price
+    /// together with the winners (sorted by user ID, so their ranks are not exposed).
+    /// Missing bids are filled with the minimum bid, which then sets the price.
+    pub fn compute_top_k(&self, k: usize, rule: UniformPriceRule) -> Result<(FheUint16, Vec<String>), AuctionError> {
+        assert!(!self.bids.is_empty(), "No bids to select winners from");
+        assert!(k > 0, "At least one unit must be auctioned");
+
+        let (users, mut entries) = self.indexed_bids();
+        // Pad so that the price position always exists.
+        while entries.len() < k + 1 {
+            entries.push(self.reserve_entry());
+        }
+        let price_position = match rule {
+            UniformPriceRule::KthHighest => k - 1,
+            UniformPriceRule::KPlusOneHighest => k,
+        };
+        sorting::select_top(&mut entries, price_position + 1);
+
+        // Only the winners' indices are decrypted; their values stay encrypted.
+        let mut winners: Vec<String> = Vec::with_capacity(k);
+        for entry in &entries[..k] {
+            winners.extend(self.reveal_bidder(&users, entry)?);
+        }
+        winners.sort();
+        Ok((entries[price_position].value.clone(), winners))
+    }
+
+    /// Rank every bid obliviously with a bitonic sorting network. Each
+    /// compare-and-swap moves the encrypted bid together with its encrypted
+    /// bidder index, so the result can be decrypted selectively (e.g. ranks only).
+    pub fn compute_ranking(&self) -> EncryptedRanking {
+        assert!(!self.bids.is_empty(), "No bids to rank");
+
+        let (users, mut entries) = self.indexed_bids();
+        // Pad to a power of two with zero-valued placeholders, which sort last.
+        while !entries.len().is_power_of_two() {
+            entries.push(EncryptedEntry {
+                value: FheUint16::encrypt(0u16, self.client_key),
+                index: FheUint16::encrypt(NO_BIDDER, self.client_key),
+            });
+        }
+        sorting::bitonic_sort(&mut entries);
+        EncryptedRanking::new(users, entries)
+    }
+
+    /// Iterate over the stored bids with their user IDs (in no particular order).
+    pub(crate) fn bids_by_user(&self) -> impl Iterator<Item = (&String, &FheUint16)> {
+        self.bids.iter()
+    }
+
+    /// Iterate over the stored encrypted bids (in no particular order).
+    pub(crate) fn encrypted_bids(&self) -> impl Iterator<Item = &FheUint16> {
+        self.bids.values()
+    }
+
+    /// Compute the encrypted sum of all bids in a 32-bit ciphertext.
+    pub fn compute_sum_encrypted(&self) -> FheUint32 {
+        // Homomorphically sum all bids in a 32‑bit ciphertext
+        let mut sum_enc = FheUint32::encrypt(0u32, self.client_key);
+        for enc_bid in self.bids.values() {
+            let enc_bid_32 = FheUint32::cast_from(enc_bid.clone());
+            sum_enc = sum_enc + enc_bid_32;  // homomorphic addition
+        }
+        sum_enc
     }
 
     /// Compute the encrypted average of all bids(always rounds down)
     pub fn compute_average_encrypted(&self) -> FheUint16 {
+        self.compute_average_with(RoundingMode::Floor).average
+    }
+
+    /// Compute the encrypted average of all bids with the given rounding mode,
+    /// returned together with the encrypted sum and the public count.
+    pub fn compute_average_with(&self, rounding: RoundingMode) -> EncryptedAverage {
         assert!(!self.bids.is_empty(), "No bids to compute average");
         // Number of bids as a clear constant
         let count = self.bids.len() as u32;
     
-        // Homomorphically sum all bids in a 32‑bit ciphertext
+        let sum_enc = self.compute_sum_encrypted();
+        let average = divide_rounded(&sum_enc, count, rounding);
+        EncryptedAverage { sum: sum_enc, count, average }
+    }
+
+    /// Compute the encrypted trimmed mean: the `trim` highest and `trim` lowest
+    /// bids are excluded obliviously (after an encrypted sort), so nobody learns
+    /// which bids were dropped.
+    pub fn compute_trimmed_average(&self, trim: usize, rounding: RoundingMode) -> EncryptedAverage {
+        let n = self.bids.len();
+        assert!(n > 2 * trim, "Not enough bids to trim {} from each end", trim);
+
+        // Placeholders added by the ranking are zero and sort after every real
+        // bid by value, so positions 0..n hold exactly the real bid values.
+        let ranking = self.compute_ranking();
         let mut sum_enc = FheUint32::encrypt(0u32, self.client_key);
-        for enc_bid in self.bids.values() {
-            let enc_bid_32 = FheUint32::cast_from(enc_bid.clone());
-            sum_enc = sum_enc + enc_bid_32;  // homomorphic addition
+        for rank in trim..n - trim {
+            sum_enc = sum_enc + FheUint32::cast_from(ranking.value_at(rank).clone());
         }
-    
-        // Homomorphically divide the encrypted sum by the clear constant `count`
-        let avg_enc_32 = sum_enc / count;
-    
-        // Cast the 32‑bit ciphertext back to 16‑bit
-        FheUint16::cast_from(avg_enc_32)
+
+        let count = (n - 2 * trim) as u32;
+        let average = divide_rounded(&sum_enc, count, rounding);
+        EncryptedAverage { sum: sum_enc, count, average }
     }
-    
 }
@@ -0,0 +1,67 @@
+// src/candle.rs
+
+// Candle auction with a hidden random closing time.
+//
+// Bidding runs for a public window, but the auction's real end is a random
+// moment inside it, drawn after the fact with tfhe's oblivious pseudo-random
+// generator and kept encrypted, so bidders cannot learn it. The server picks
+// the seed and holds the client key, so it is trusted not to decrypt the
+// cutoff or redraw it until it likes the result. Each bidder's bid is
+// the last one they submitted before that moment, selected homomorphically
+// from their timestamped history; later submissions are discarded.
+
+use rand::{thread_rng, Rng};
+use tfhe::{FheUint16, FheUint64, Seed};
+use tfhe::prelude::*;
+use crate::auction::{Auction, AuctionError};
+use crate::disclosure::Disclosure;
+use crate::leakage::AUCTIONEER;
+
+impl<'a> Auction<'a> {
+    /// Compute the encrypted maximum over the bids standing at a hidden random
+    /// cutoff in `[0, window_secs)` seconds after opening. Returns the
+    /// encrypted maximum and the bidder(s) holding it; bidders with no bid
+    /// before the cutoff cannot win. The cutoff itself is never decrypted.
+    /// Fails if the disclosure policy does not allow revealing the winner identity.
+    pub fn compute_candle_max(&self, window_secs: u64) -> Result<(FheUint16, Vec<String>), AuctionError> {
+        assert!(self.count_bids() > 0, "No bids to compute max from");
+        assert!(window_secs > 0, "The bidding window must not be empty");
+        let client_key = self.client_key();
+
+        // Uniform 16-bit randomness scaled into the window. The seed comes
+        // from the server's RNG; the draw is hidden from bidders, not from a
+        // server that chooses to decrypt it.
+        let random = FheUint16::generate_oblivious_pseudo_random(Seed(thread_rng().gen::<u128>()));
+        let cutoff = FheUint64::cast_from(random) * window_secs / 65536u64;
+
+        // Each bidder's standing bid: the last history entry at or before the
+        // cutoff. `add_bid_at` keeps each history sorted by time, so the last
+        // selected entry is the most recent bid before the cutoff.
+        let zero = FheUint16::encrypt(0u16, client_key);
+        let mut standing: Vec<(String, FheUint16)> = Vec::new();
+        for (user, _) in self.bids_by_user() {
+            let mut value = zero.clone();
+            for timed in self.bid_history(user) {
+                let in_time = cutoff.ge(timed.at);
+                value = in_time.select(&timed.value, &value);
+            }
+            standing.push((user.clone(), value));
+        }
+
+        let mut current_max = standing[0].1.clone();
+        for (_, value) in &standing[1..] {
+            current_max = current_max.max(value);
+        }
+
+        // A bidder wins if they hold the maximum and had a bid in time.
+        let mut top_users: Vec<String> = Vec::new();
+        for (user, value) in &standing {
+            let is_top = &value.eq(&current_max) & &value.gt(0u16);
+            let item = format!("{} holds the highest bid before the candle went out", user);
+            if self.reveal_to(AUCTIONEER, Disclosure::WinnerIdentity, &item, &is_top)? {
+                top_users.push(user.clone());
+            }
+        }
+        Ok((current_max, top_users))
+    }
+}
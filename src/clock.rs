@@ -0,0 +1,56 @@
+// src/clock.rs
+
+// Time sources for the auction lifecycle. Auctions read the time through the
+// Clock trait, so tests can drive deadlines with a MockClock.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::SystemTime;
+
+/// A source of the current time, in whole seconds.
+pub trait Clock {
+    fn now(&self) -> u64;
+}
+
+/// Wall-clock time: seconds since the Unix epoch.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+}
+
+/// A manually driven clock. Clones share the same time, so a test can keep
+/// one handle and give another to the auction.
+#[derive(Clone, Debug, Default)]
+pub struct MockClock {
+    now: Rc<Cell<u64>>,
+}
+
+impl MockClock {
+    /// Create a clock showing `start` seconds.
+    pub fn new(start: u64) -> Self {
+        MockClock { now: Rc::new(Cell::new(start)) }
+    }
+
+    /// Move the clock forward by `secs` seconds.
+    pub fn advance(&self, secs: u64) {
+        self.now.set(self.now.get() + secs);
+    }
+
+    /// Set the clock to `secs` seconds.
+    pub fn set(&self, secs: u64) {
+        self.now.set(secs);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> u64 {
+        self.now.get()
+    }
+}
@@ -0,0 +1,165 @@
+// src/combinatorial.rs
+
+// Small combinatorial auctions with encrypted bundle bids.
+//
+// Bidders submit an encrypted value for each bundle of items they want, and
+// each bidder can win at most one of their bundles. The server enumerates
+// every feasible allocation (disjoint bundles, one per bidder at most), sums
+// the encrypted values of each, and picks the revenue-maximising allocation
+// with an encrypted argmax. Only the winning allocation and its total are
+// decrypted, through an Auction book so the disclosure policy and leakage log
+// apply as in every other mode.
+
+use tfhe::{ClientKey, FheUint16, FheUint32};
+use tfhe::prelude::*;
+use crate::auction::{Auction, AuctionError};
+use crate::disclosure::{Disclosure, DisclosurePolicy};
+use crate::leakage::AUCTIONEER;
+
+/// Maximum number of items; the number of allocations grows quickly beyond it.
+pub const MAX_ITEMS: usize = 4;
+
+/// One bidder's encrypted value for one bundle.
+struct BundleBid {
+    bidder: String,
+    items: u8,          // Bit mask over the auction's items
+    value: FheUint16,
+}
+
+/// The revenue-maximising allocation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CombinatorialOutcome {
+    pub allocation: Vec<(String, Vec<String>)>,  // Winning bidders and the items they get
+    pub revenue: u32,                            // Total value of the allocation
+}
+
+pub struct CombinatorialAuction<'a> {
+    book: Auction<'a>,         // Key handling, disclosure policy and leakage log
+    items: Vec<String>,        // Item names; position i is bit i of a bundle mask
+    bids: Vec<BundleBid>,      // Every bundle bid, in submission order
+}
+
+impl<'a> CombinatorialAuction<'a> {
+    /// Create an auction for the given items (at most `MAX_ITEMS`).
+    pub fn new(client_key: &'a ClientKey, items: &[&str]) -> Self {
+        assert!(
+            !items.is_empty() && items.len() <= MAX_ITEMS,
+            "A combinatorial auction needs between 1 and {} items",
+            MAX_ITEMS
+        );
+        CombinatorialAuction {
+            book: Auction::new(client_key, 0),
+            items: items.iter().map(|item| item.to_string()).collect(),
+            bids: Vec::new(),
+        }
+    }
+
+    /// Set which outputs may be decrypted.
+    pub fn with_disclosure_policy(mut self, policy: DisclosurePolicy) -> Self {
+        self.book = self.book.with_disclosure_policy(policy);
+        self
+    }
+
+    /// Return the underlying book (for its leakage log or disclosure policy).
+    pub fn book(&self) -> &Auction<'a> {
+        &self.book
+    }
+
+    /// Encrypt and store a bidder's value for a bundle of items.
+    /// A later bid by the same bidder for the same bundle replaces the earlier one.
+    pub fn add_bundle_bid(&mut self, user_id: &str, bundle: &[&str], value: u16) -> Result<(), AuctionError> {
+        assert!(!bundle.is_empty(), "A bundle must contain at least one item");
+        let mut items = 0u8;
+        for name in bundle {
+            let position = self
+                .items
+                .iter()
+                .position(|item| item == name)
+                .ok_or_else(|| AuctionError::UnknownItem(name.to_string()))?;
+            items |= 1 << position;
+        }
+
+        let enc_value = FheUint16::encrypt(value, self.book.client_key());
+        match self.bids.iter_mut().find(|b| b.bidder == user_id && b.items == items) {
+            Some(existing) => existing.value = enc_value,
+            None => self.bids.push(BundleBid { bidder: user_id.to_string(), items, value: enc_value }),
+        }
+        Ok(())
+    }
+
+    /// Enumerate every feasible allocation as a list of bid positions.
+    fn feasible_allocations(&self) -> Vec<Vec<usize>> {
+        let mut bidders: Vec<&str> = self.bids.iter().map(|b| b.bidder.as_str()).collect();
+        bidders.sort();
+        bidders.dedup();
+
+        let mut allocations = Vec::new();
+        self.extend_allocations(&bidders, 0, &mut Vec::new(), &mut allocations);
+        allocations
+    }
+
+    /// Recursively assign each bidder either nothing or one of their bundles
+    /// that does not overlap the items already allocated.
+    fn extend_allocations(
+        &self,
+        bidders: &[&str],
+        used: u8,
+        chosen: &mut Vec<usize>,
+        allocations: &mut Vec<Vec<usize>>,
+    ) {
+        let Some((bidder, rest)) = bidders.split_first() else {
+            allocations.push(chosen.clone());
+            return;
+        };
+        // The bidder wins nothing.
+        self.extend_allocations(rest, used, chosen, allocations);
+        // The bidder wins one of their bundles.
+        for (position, bid) in self.bids.iter().enumerate() {
+            if bid.bidder == *bidder && bid.items & used == 0 {
+                chosen.push(position);
+                self.extend_allocations(rest, used | bid.items, chosen, allocations);
+                chosen.pop();
+            }
+        }
+    }
+
+    /// Find the revenue-maximising allocation. Ties go to the allocation
+    /// enumerated first (the one leaving bidders unassigned earliest).
+    pub fn compute_best_allocation(&self) -> Result<CombinatorialOutcome, AuctionError> {
+        let client_key = self.book.client_key();
+        let allocations = self.feasible_allocations();
+
+        // Encrypted revenue of every allocation, then an encrypted argmax. The
+        // index is 32 bits wide: with MAX_ITEMS items and many single-item
+        // bidders the number of allocations exceeds 16 bits.
+        let mut best_revenue = FheUint32::encrypt(0u32, client_key);
+        let mut best_index = FheUint32::encrypt(0u32, client_key);
+        for (index, allocation) in allocations.iter().enumerate() {
+            let mut revenue = FheUint32::encrypt(0u32, client_key);
+            for &position in allocation {
+                revenue = revenue + FheUint32::cast_from(self.bids[position].value.clone());
+            }
+            let better = revenue.gt(&best_revenue);
+            best_revenue = better.select(&revenue, &best_revenue);
+            best_index = better.select(&FheUint32::encrypt(index as u32, client_key), &best_index);
+        }
+
+        // Only the winning allocation and its total are decrypted.
+        let index: u32 =
+            self.book.reveal_to(AUCTIONEER, Disclosure::WinnerIdentity, "winning allocation", &best_index)?;
+        let revenue: u32 =
+            self.book.reveal_to(AUCTIONEER, Disclosure::ClearingPrice, "allocation revenue", &best_revenue)?;
+        let allocation = allocations[index as usize]
+            .iter()
+            .map(|&position| {
+                let bid = &self.bids[position];
+                let items = (0..self.items.len())
+                    .filter(|i| bid.items & (1 << i) != 0)
+                    .map(|i| self.items[i].clone())
+                    .collect();
+                (bid.bidder.clone(), items)
+            })
+            .collect();
+        Ok(CombinatorialOutcome { allocation, revenue })
+    }
+}
@@ -0,0 +1,145 @@
+// src/consortium.rs
+
+// Consortium (joint) bids.
+//
+// A consortium is registered under its own name with a fixed set of member
+// bidders. Each member submits an encrypted share, and once the shares are in
+// the consortium's bid is submitted as their homomorphic sum, through the same
+// ingestion path as any other bid (deadline, amendment policy, buy-it-now and
+// history), so every auction mode can use it. Once the consortium holds the
+// highest bid, each member can get their own contribution re-encrypted under
+// the public key they registered, so other members never see it. Like the
+// all-pay payments, the re-encryption decrypts the share with the auction's
+// client key, so the auctioneer learns it, and the leakage log says so.
+
+use std::collections::{BTreeSet, HashMap};
+use tfhe::{FheUint16, FheUint32};
+use tfhe::prelude::*;
+use crate::auction::{Auction, AuctionError};
+use crate::disclosure::Disclosure;
+use crate::leakage::AUCTIONEER;
+
+/// Members of a consortium and their encrypted shares.
+pub(crate) struct Consortium {
+    members: BTreeSet<String>,             // Bidders allowed to contribute
+    shares: HashMap<String, FheUint16>,    // Encrypted share per member who has contributed
+    submitted: HashMap<String, FheUint16>, // Shares included in the last submitted bid
+}
+
+impl<'a> Auction<'a> {
+    /// Register a consortium bidding jointly for `members`. The consortium and
+    /// its members are added to the registry; registering the same name again
+    /// replaces its members and discards earlier shares.
+    pub fn register_consortium(&mut self, name: &str, members: &[&str]) -> Result<(), AuctionError> {
+        assert!(!members.is_empty(), "A consortium needs at least one member");
+        if self.close_if_expired() {
+            return Err(AuctionError::AuctionClosed);
+        }
+        self.register_bidder(name);
+        for member in members {
+            self.register_bidder(member);
+        }
+        let consortium = Consortium {
+            members: members.iter().map(|m| m.to_string()).collect(),
+            shares: HashMap::new(),
+            submitted: HashMap::new(),
+        };
+        self.consortia_mut().insert(name.to_string(), consortium);
+        Ok(())
+    }
+
+    /// Return the members of a consortium, sorted by ID.
+    pub fn consortium_members(&self, name: &str) -> Option<Vec<String>> {
+        self.consortia()
+            .get(name)
+            .map(|c| c.members.iter().cloned().collect())
+    }
+
+    /// Encrypt and store a member's share (replacing an earlier one). The
+    /// consortium's bid only changes when it is next submitted.
+    pub fn add_consortium_share(&mut self, name: &str, member: &str, share: u16) -> Result<(), AuctionError> {
+        if self.close_if_expired() {
+            return Err(AuctionError::AuctionClosed);
+        }
+        let client_key = self.client_key();
+        let consortium = self
+            .consortia_mut()
+            .get_mut(name)
+            .ok_or_else(|| AuctionError::NotRegistered(name.to_string()))?;
+        if !consortium.members.contains(member) {
+            return Err(AuctionError::NotRegistered(member.to_string()));
+        }
+        consortium
+            .shares
+            .insert(member.to_string(), FheUint16::encrypt(share, client_key));
+        Ok(())
+    }
+
+    /// Submit the consortium's bid: the encrypted sum of the shares so far,
+    /// saturated obliviously at the largest 16-bit bid instead of wrapping.
+    /// The bid goes through the normal ingestion path, so submitting again
+    /// after a share changes is an amendment under the auction's policy.
+    pub fn submit_consortium_bid(&mut self, name: &str) -> Result<(), AuctionError> {
+        let client_key = self.client_key();
+        let consortium = self
+            .consortia()
+            .get(name)
+            .ok_or_else(|| AuctionError::NotRegistered(name.to_string()))?;
+        if consortium.shares.is_empty() {
+            return Err(AuctionError::NoBid(name.to_string()));
+        }
+
+        let mut total = FheUint32::encrypt(0u32, client_key);
+        for enc_share in consortium.shares.values() {
+            total = total + FheUint32::cast_from(enc_share.clone());
+        }
+        let cap = FheUint32::encrypt(u16::MAX as u32, client_key);
+        let bid = FheUint16::cast_from(total.min(&cap));
+        let shares = consortium.shares.clone();
+
+        let at = self.elapsed_secs();
+        self.add_encrypted_bid_at(name, bid, at)?;
+        if let Some(consortium) = self.consortia_mut().get_mut(name) {
+            consortium.submitted = shares;
+        }
+        Ok(())
+    }
+
+    /// Give one member of a winning consortium their own contribution, as
+    /// included in the consortium's last submitted bid, encrypted under the
+    /// member's registered public key. The encrypted "holds the highest bid"
+    /// bit of the consortium is decrypted to check that it won, then the share
+    /// is re-encrypted (see `reencrypt_for`). Fails with `NotRegistered` if the
+    /// member has no key and with `NotWinner` if another bidder holds the
+    /// highest bid.
+    pub fn reveal_contribution(&self, name: &str, member: &str) -> Result<FheUint16, AuctionError> {
+        let consortium = self
+            .consortia()
+            .get(name)
+            .ok_or_else(|| AuctionError::NotRegistered(name.to_string()))?;
+        let share = consortium
+            .submitted
+            .get(member)
+            .ok_or_else(|| AuctionError::NoBid(member.to_string()))?;
+        if self.bidder_key(member).is_none() {
+            return Err(AuctionError::NotRegistered(member.to_string()));
+        }
+        let (_, bid) = self
+            .bids_by_user()
+            .find(|(user, _)| user.as_str() == name)
+            .ok_or_else(|| AuctionError::NoBid(name.to_string()))?;
+
+        let mut highest = bid.clone();
+        for enc_bid in self.encrypted_bids() {
+            highest = highest.max(enc_bid);
+        }
+        let item = format!("{} holds the highest bid", name);
+        let won: bool = self.reveal_to(AUCTIONEER, Disclosure::WinnerIdentity, &item, &bid.eq(&highest))?;
+        if !won {
+            return Err(AuctionError::NotWinner(name.to_string()));
+        }
+
+        let item = format!("{}'s contribution to {}", member, name);
+        self.reencrypt_for(member, Disclosure::Contributions, &item, share)
+    }
+}
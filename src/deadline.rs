@@ -0,0 +1,63 @@
+// src/deadline.rs
+
+// Soft-close deadline scheduling for timed auctions.
+//
+// An auction closes a fixed duration after it opens, but a valid bid that
+// arrives within the extension window before the deadline pushes the deadline
+// back, up to a maximum number of extensions. This stops last-second sniping.
+// All times are in seconds since the auction opened.
+
+/// Configuration of a soft close.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SoftClose {
+    pub duration_secs: u64,          // Initial length of the auction
+    pub extension_window_secs: u64,  // A bid this close to the deadline extends it
+    pub extension_secs: u64,         // How far each extension moves the deadline
+    pub max_extensions: u32,         // Extensions allowed in total
+}
+
+/// Tracks the current deadline of one auction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeadlineScheduler {
+    config: SoftClose,
+    deadline: u64,         // Current deadline
+    extensions_used: u32,  // Extensions granted so far
+}
+
+impl DeadlineScheduler {
+    /// Start scheduling with the initial deadline at `duration_secs`.
+    pub fn new(config: SoftClose) -> Self {
+        DeadlineScheduler {
+            config,
+            deadline: config.duration_secs,
+            extensions_used: 0,
+        }
+    }
+
+    /// Return the current deadline.
+    pub fn deadline(&self) -> u64 {
+        self.deadline
+    }
+
+    /// Return the number of extensions granted so far.
+    pub fn extensions_used(&self) -> u32 {
+        self.extensions_used
+    }
+
+    /// Return true if bids are still accepted at time `at` (the deadline itself is too late).
+    pub fn is_open(&self, at: u64) -> bool {
+        at < self.deadline
+    }
+
+    /// Register a valid bid accepted at time `at`, extending the deadline if
+    /// the bid falls inside the extension window. Returns true if it did.
+    pub fn register_bid(&mut self, at: u64) -> bool {
+        let in_window = at + self.config.extension_window_secs >= self.deadline;
+        if in_window && self.extensions_used < self.config.max_extensions {
+            self.deadline += self.config.extension_secs;
+            self.extensions_used += 1;
+            return true;
+        }
+        false
+    }
+}
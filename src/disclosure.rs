@@ -0,0 +1,103 @@
+// src/disclosure.rs
+
+// Declares which auction outputs may be decrypted. The auction consults the
+// policy before every decryption it performs on the auctioneer's behalf.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// An output of the auction that can be decrypted and published.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Disclosure {
+    /// Which bidder(s) won.
+    WinnerIdentity,
+    /// The price the winner(s) pay, or the highest bid.
+    ClearingPrice,
+    /// The average bid.
+    Average,
+    /// The number of bids received.
+    BidCount,
+    /// The order of bidders from highest to lowest bid.
+    Ranks,
+    /// Other aggregate statistics (median, spread, histogram).
+    Statistics,
+    /// Whether some bid has met the buy-it-now price.
+    BuyItNow,
+    /// Each consortium member's share of a joint bid (told only to that member).
+    Contributions,
+}
+
+impl Disclosure {
+    /// Every kind of output, in display order.
+    pub const ALL: [Disclosure; 8] = [
+        Disclosure::WinnerIdentity,
+        Disclosure::ClearingPrice,
+        Disclosure::Average,
+        Disclosure::BidCount,
+        Disclosure::Ranks,
+        Disclosure::Statistics,
+        Disclosure::BuyItNow,
+        Disclosure::Contributions,
+    ];
+}
+
+impl fmt::Display for Disclosure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Disclosure::WinnerIdentity => "winner identity",
+            Disclosure::ClearingPrice => "clearing price",
+            Disclosure::Average => "average bid",
+            Disclosure::BidCount => "number of bids",
+            Disclosure::Ranks => "bidder ranks",
+            Disclosure::Statistics => "bid statistics",
+            Disclosure::BuyItNow => "buy-it-now reached",
+            Disclosure::Contributions => "consortium contributions",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The set of outputs an auction is allowed to reveal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisclosurePolicy {
+    allowed: BTreeSet<Disclosure>,
+}
+
+impl DisclosurePolicy {
+    /// A policy that reveals nothing.
+    pub fn none() -> Self {
+        DisclosurePolicy { allowed: BTreeSet::new() }
+    }
+
+    /// A policy that reveals every kind of output.
+    pub fn all() -> Self {
+        DisclosurePolicy { allowed: Disclosure::ALL.iter().copied().collect() }
+    }
+
+    /// Allow one more kind of output.
+    pub fn allow(mut self, disclosure: Disclosure) -> Self {
+        self.allowed.insert(disclosure);
+        self
+    }
+
+    /// Return true if the policy allows the given output to be revealed.
+    pub fn allows(&self, disclosure: Disclosure) -> bool {
+        self.allowed.contains(&disclosure)
+    }
+}
+
+impl Default for DisclosurePolicy {
+    fn default() -> Self {
+        DisclosurePolicy::all()
+    }
+}
+
+impl fmt::Display for DisclosurePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.allowed.is_empty() {
+            return write!(f, "nothing");
+        }
+        let names: Vec<String> = self.allowed.iter().map(|d| d.to_string()).collect();
+        write!(f, "{}", names.join(", "))
+    }
+}
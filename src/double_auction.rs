@@ -0,0 +1,123 @@
+// src/double_auction.rs
+
+// Sealed-bid double auction for buyers and sellers.
+//
+// Buy bids and sell asks are kept in two Auction books, so both stay encrypted
+// and reuse the same storage, disclosure policy and leakage accounting. Bids
+// are sorted obliviously from highest to lowest and asks from lowest to
+// highest; the i-th buyer trades with the i-th seller while bid >= ask. The
+// clearing price is set k-double-auction style from the last matched pair:
+//     price = (k * bid + (100 - k) * ask) / 100,   with k in percent.
+
+use tfhe::{ClientKey, FheUint16, FheUint32};
+use tfhe::prelude::*;
+use crate::auction::{Auction, AuctionError, NO_BIDDER};
+use crate::disclosure::Disclosure;
+use crate::leakage::AUCTIONEER;
+use crate::sorting::{self, EncryptedEntry};
+
+/// Outcome of a double auction.
+pub struct DoubleAuctionOutcome {
+    pub trades: usize,                     // Number of matched trades
+    pub price: Option<FheUint16>,          // Encrypted clearing price (None if nothing traded)
+    pub matches: Vec<(String, String)>,    // (buyer, seller) pairs, from the best-matched pair down
+}
+
+pub struct DoubleAuction<'a> {
+    buyers: Auction<'a>,    // Encrypted buy bids
+    sellers: Auction<'a>,   // Encrypted sell asks
+    k_percent: u8,          // Weight of the bid in the clearing price, in percent
+}
+
+/// Sort the bids of a book obliviously in descending order, padding to a power
+/// of two with placeholder entries of the given value.
+fn sorted_book(book: &Auction, pad_value: u16) -> (Vec<String>, Vec<EncryptedEntry>) {
+    let client_key = book.client_key();
+    let (users, mut entries) = book.indexed_bids();
+    while !entries.len().is_power_of_two() {
+        entries.push(EncryptedEntry {
+            value: FheUint16::encrypt(pad_value, client_key),
+            index: FheUint16::encrypt(NO_BIDDER, client_key),
+        });
+    }
+    sorting::bitonic_sort(&mut entries);
+    (users, entries)
+}
+
+impl<'a> DoubleAuction<'a> {
+    /// Create an empty double auction that splits the surplus evenly (k = 50%).
+    pub fn new(client_key: &'a ClientKey) -> Self {
+        DoubleAuction {
+            buyers: Auction::new(client_key, 0),
+            sellers: Auction::new(client_key, 0),
+            k_percent: 50,
+        }
+    }
+
+    /// Set the weight of the marginal bid in the clearing price (0 = ask, 100 = bid).
+    pub fn with_k_percent(mut self, k_percent: u8) -> Self {
+        assert!(k_percent <= 100, "k must be between 0 and 100 percent");
+        self.k_percent = k_percent;
+        self
+    }
+
+    /// Encrypt and store a buyer's bid.
+    pub fn add_buy_bid(&mut self, user_id: &str, bid_value: u16) -> Result<(), AuctionError> {
+        self.buyers.add_bid(user_id, bid_value)
+    }
+
+    /// Encrypt and store a seller's ask.
+    pub fn add_sell_ask(&mut self, user_id: &str, ask_value: u16) -> Result<(), AuctionError> {
+        self.sellers.add_bid(user_id, ask_value)
+    }
+
+    /// Return the buyers' book (for its leakage log or disclosure policy).
+    pub fn buyers(&self) -> &Auction<'a> {
+        &self.buyers
+    }
+
+    /// Return the sellers' book.
+    pub fn sellers(&self) -> &Auction<'a> {
+        &self.sellers
+    }
+
+    /// Match buyers and sellers. Only the number of trades, the matched pairs
+    /// and (encrypted) the clearing price come out; unmatched orders stay hidden.
+    pub fn compute_clearing(&self) -> Result<DoubleAuctionOutcome, AuctionError> {
+        let client_key = self.buyers.client_key();
+        // Bids descending, placeholders (0) last.
+        let (buyer_ids, bids) = sorted_book(&self.buyers, 0);
+        // Asks ascending: sort descending with placeholders (max) first, then reverse.
+        let (seller_ids, mut asks) = sorted_book(&self.sellers, u16::MAX);
+        asks.reverse();
+
+        // Trades happen on a prefix of the pairs; count them homomorphically.
+        let pairs = self.buyers.count_bids().min(self.sellers.count_bids());
+        let mut trades_enc = FheUint16::encrypt(0u16, client_key);
+        for (bid, ask) in bids.iter().zip(asks.iter()).take(pairs) {
+            trades_enc = trades_enc + FheUint16::cast_from(bid.value.ge(&ask.value));
+        }
+        let trades: u16 =
+            self.buyers.reveal_to(AUCTIONEER, Disclosure::ClearingPrice, "number of trades", &trades_enc)?;
+        let trades = trades as usize;
+        if trades == 0 {
+            return Ok(DoubleAuctionOutcome { trades, price: None, matches: Vec::new() });
+        }
+
+        // Price from the marginal (last matched) pair, weighted by k.
+        let marginal_bid = FheUint32::cast_from(bids[trades - 1].value.clone());
+        let marginal_ask = FheUint32::cast_from(asks[trades - 1].value.clone());
+        let weighted = marginal_bid * self.k_percent as u32 + marginal_ask * (100 - self.k_percent) as u32;
+        let price = FheUint16::cast_from(weighted / 100u32);
+
+        let mut matches = Vec::with_capacity(trades);
+        for (bid, ask) in bids.iter().zip(asks.iter()).take(trades) {
+            let buyer = self.buyers.reveal_bidder(&buyer_ids, bid)?;
+            let seller = self.sellers.reveal_bidder(&seller_ids, ask)?;
+            if let (Some(buyer), Some(seller)) = (buyer, seller) {
+                matches.push((buyer, seller));
+            }
+        }
+        Ok(DoubleAuctionOutcome { trades, price: Some(price), matches })
+    }
+}
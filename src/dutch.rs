@@ -0,0 +1,61 @@
+// src/dutch.rs
+
+// Dutch (descending clock) auction.
+//
+// Each stored bid is the bidder's encrypted acceptance threshold: the highest
+// price they are willing to pay, so any clock price at or below it is
+// accepted. The clock walks a public price schedule downwards, and at each
+// step only a single encrypted "someone accepts" bit is decrypted. The
+// accepting bidder is identified once, at the end.
+
+use tfhe::FheBool;
+use tfhe::prelude::*;
+use crate::auction::{Auction, AuctionError};
+use crate::disclosure::Disclosure;
+use crate::leakage::AUCTIONEER;
+use crate::sorting;
+
+/// Result of a Dutch auction in which someone accepted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DutchOutcome {
+    pub price: u16,              // Clock price at which the lot was accepted
+    pub winner: Option<String>,  // Accepting bidder
+    pub steps: usize,            // Number of clock steps taken, including the accepting one
+}
+
+impl<'a> Auction<'a> {
+    /// Run a descending clock over `schedule` (strictly decreasing prices).
+    /// Returns None if nobody accepts any price on the schedule. When several
+    /// bidders accept at the same step, the one with the highest threshold wins.
+    pub fn run_dutch_clock(&self, schedule: &[u16]) -> Result<Option<DutchOutcome>, AuctionError> {
+        assert!(self.count_bids() > 0, "No bids to run the clock against");
+        assert!(
+            schedule.windows(2).all(|w| w[0] > w[1]),
+            "The price schedule must be strictly decreasing"
+        );
+
+        for (step, &price) in schedule.iter().enumerate() {
+            // Encrypted OR over all "threshold >= price" bits.
+            let mut iter = self.encrypted_bids();
+            let mut someone_accepts: FheBool = iter.next().unwrap().ge(price);
+            for enc_bid in iter {
+                someone_accepts = &someone_accepts | &enc_bid.ge(price);
+            }
+
+            let item = format!("someone accepts at {}", price);
+            let accepted: bool = self.reveal_to(AUCTIONEER, Disclosure::ClearingPrice, &item, &someone_accepts)?;
+            if accepted {
+                // The highest threshold is at or above the price, so it belongs
+                // to an accepting bidder; only its index is decrypted.
+                let (users, mut entries) = self.indexed_bids();
+                sorting::select_top(&mut entries, 1);
+                return Ok(Some(DutchOutcome {
+                    price,
+                    winner: self.reveal_bidder(&users, &entries[0])?,
+                    steps: step + 1,
+                }));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,40 @@
+// src/english.rs
+
+// English (ascending) auction with proxy bidding.
+//
+// Each stored bid is the bidder's encrypted maximum. The system bids on every
+// bidder's behalf, so the outcome is the same as an ascending auction in which
+// each proxy stops at its maximum: the highest maximum wins, paying one
+// increment above the second-highest maximum, but never more than its own.
+
+use tfhe::{FheUint16, FheUint32};
+use tfhe::prelude::*;
+use crate::auction::{Auction, AuctionError};
+use crate::sorting;
+
+impl<'a> Auction<'a> {
+    /// Compute the proxy-bidding outcome for a public bid increment.
+    /// Returns the encrypted price and the winner; no maximum is decrypted.
+    /// Fails if the disclosure policy does not allow revealing the winner.
+    /// With a single bidder the minimum bid stands in for the second maximum,
+    /// and on equal maxima the bidder whose ID sorts first wins.
+    pub fn compute_proxy_outcome(&self, increment: u16) -> Result<(FheUint16, Vec<String>), AuctionError> {
+        assert!(self.count_bids() > 0, "No bids to compute the proxy outcome from");
+
+        let (users, mut entries) = self.indexed_bids();
+        if entries.len() < 2 {
+            entries.push(self.reserve_entry());
+        }
+        // Only the two highest maxima are needed.
+        sorting::select_top(&mut entries, 2);
+
+        // second + increment, capped at the winner's maximum. Computed in
+        // 32 bits so that adding the increment cannot wrap around.
+        let raised = FheUint32::cast_from(entries[1].value.clone()) + increment as u32;
+        let cap = FheUint32::cast_from(entries[0].value.clone());
+        let price = FheUint16::cast_from(raised.min(&cap));
+
+        let winner = self.reveal_bidder(&users, &entries[0])?;
+        Ok((price, winner.into_iter().collect()))
+    }
+}
@@ -0,0 +1,51 @@
+// src/gsp.rs
+
+// Generalized second-price (GSP) auction for k ranked slots.
+//
+// The k + 1 highest bids are selected obliviously together with their
+// encrypted bidder indices. The bidder in slot i pays the bid ranked i + 1,
+// so only the k slot holders and the k payments are decrypted; every other
+// bid value stays encrypted.
+
+use crate::auction::{Auction, AuctionError};
+use crate::disclosure::Disclosure;
+use crate::leakage::AUCTIONEER;
+use crate::sorting;
+
+/// A slot and who won it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlotAssignment {
+    pub slot: usize,               // 0 = best slot
+    pub bidder: Option<String>,    // Slot holder
+    pub payment: u16,              // Next-highest bid, or the minimum bid if there is none
+}
+
+impl<'a> Auction<'a> {
+    /// Assign up to `slots` ranked slots by GSP. Slots beyond the number of
+    /// bidders stay unassigned, and a missing next-highest bid is replaced by
+    /// the minimum bid.
+    pub fn compute_gsp(&self, slots: usize) -> Result<Vec<SlotAssignment>, AuctionError> {
+        assert!(self.count_bids() > 0, "No bids to assign slots from");
+        assert!(slots > 0, "At least one slot must be auctioned");
+
+        let (users, mut entries) = self.indexed_bids();
+        while entries.len() < slots + 1 {
+            entries.push(self.reserve_entry());
+        }
+        sorting::select_top(&mut entries, slots + 1);
+
+        let filled = slots.min(self.count_bids());
+        let mut assignments = Vec::with_capacity(filled);
+        for slot in 0..filled {
+            let item = format!("payment for slot {}", slot);
+            let payment: u16 =
+                self.reveal_to(AUCTIONEER, Disclosure::ClearingPrice, &item, &entries[slot + 1].value)?;
+            assignments.push(SlotAssignment {
+                slot,
+                bidder: self.reveal_bidder(&users, &entries[slot])?,
+                payment,
+            });
+        }
+        Ok(assignments)
+    }
+}
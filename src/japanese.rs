@@ -0,0 +1,80 @@
+// src/japanese.rs
+
+// Japanese (ascending clock) auction.
+//
+// Each stored bid is the bidder's encrypted drop-out point: the highest price
+// at which they stay in. The clock walks a public price schedule upwards and
+// at each step computes only the encrypted number of bidders still active.
+// The single bit "at most one bidder left" is decrypted per step, plus one
+// final bit telling one from zero, so no drop-out point is ever disclosed.
+
+use tfhe::FheUint16;
+use tfhe::prelude::*;
+use crate::auction::{Auction, AuctionError};
+use crate::disclosure::Disclosure;
+use crate::leakage::AUCTIONEER;
+use crate::sorting;
+
+/// Result of a Japanese auction that produced a winner.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JapaneseOutcome {
+    pub price: u16,              // Clearing price
+    pub winner: Option<String>,  // Last bidder standing
+    pub steps: usize,            // Number of clock steps taken
+}
+
+impl<'a> Auction<'a> {
+    /// Run an ascending clock over `schedule` (strictly increasing prices).
+    ///
+    /// When exactly one bidder is left at a price, they win at that price. When
+    /// the last bidders all drop out at the same step, the clock falls back to
+    /// the previous price and the one with the highest drop-out point wins
+    /// (ties go to the bidder whose ID sorts first). Returns None if nobody is
+    /// active at the first price or several are still active at the last one.
+    pub fn run_japanese_clock(&self, schedule: &[u16]) -> Result<Option<JapaneseOutcome>, AuctionError> {
+        assert!(self.count_bids() > 0, "No bids to run the clock against");
+        assert!(
+            schedule.windows(2).all(|w| w[0] < w[1]),
+            "The price schedule must be strictly increasing"
+        );
+        let client_key = self.client_key();
+
+        for (step, &price) in schedule.iter().enumerate() {
+            // Encrypted count of bidders whose drop-out point is at or above the price.
+            let mut active = FheUint16::encrypt(0u16, client_key);
+            for enc_bid in self.encrypted_bids() {
+                active = active + FheUint16::cast_from(enc_bid.ge(price));
+            }
+
+            let item = format!("at most one bidder active at {}", price);
+            let at_most_one: bool =
+                self.reveal_to(AUCTIONEER, Disclosure::ClearingPrice, &item, &active.le(1u16))?;
+            if !at_most_one {
+                continue;
+            }
+
+            let item = format!("exactly one bidder active at {}", price);
+            let exactly_one: bool =
+                self.reveal_to(AUCTIONEER, Disclosure::ClearingPrice, &item, &active.eq(1u16))?;
+            let clearing_price = if exactly_one {
+                price
+            } else if step > 0 {
+                schedule[step - 1]
+            } else {
+                // Nobody was willing to pay the opening price.
+                return Ok(None);
+            };
+
+            // Either way the winner holds the highest drop-out point; only its
+            // bidder index is decrypted.
+            let (users, mut entries) = self.indexed_bids();
+            sorting::select_top(&mut entries, 1);
+            return Ok(Some(JapaneseOutcome {
+                price: clearing_price,
+                winner: self.reveal_bidder(&users, &entries[0])?,
+                steps: step + 1,
+            }));
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,114 @@
+// src/leakage.rs
+
+// Accounting of everything an auction run has decrypted, and for whom.
+// The auction records each decryption it performs; the CLI records values it
+// passes on to other parties. At settlement the log is turned into a report.
+
+use std::fmt::Write;
+use crate::disclosure::Disclosure;
+
+/// Party that performs decryptions on the auction's behalf.
+pub const AUCTIONEER: &str = "auctioneer";
+
+/// One piece of information learned by one party.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LeakageRecord {
+    pub party: String,          // Who learned it
+    pub output: Disclosure,     // Which kind of output it belongs to
+    pub item: String,           // What exactly was learned
+    pub value: String,          // The decrypted value, as text
+}
+
+/// Every disclosure of an auction run, in the order they happened.
+#[derive(Clone, Debug, Default)]
+pub struct LeakageLog {
+    records: Vec<LeakageRecord>,
+}
+
+impl LeakageLog {
+    /// Record that `party` learned `value` for `item`.
+    pub fn record(&mut self, party: &str, output: Disclosure, item: &str, value: String) {
+        self.records.push(LeakageRecord {
+            party: party.to_string(),
+            output,
+            item: item.to_string(),
+            value,
+        });
+    }
+
+    /// Return every record, in order.
+    pub fn records(&self) -> &[LeakageRecord] {
+        &self.records
+    }
+
+    /// Return the parties that learned something, in order of first appearance.
+    fn parties(&self) -> Vec<&str> {
+        let mut parties: Vec<&str> = Vec::new();
+        for record in &self.records {
+            if !parties.contains(&record.party.as_str()) {
+                parties.push(&record.party);
+            }
+        }
+        parties
+    }
+
+    /// Human-readable report listing what each party learned.
+    pub fn to_text(&self) -> String {
+        let mut out = String::from("===== Leakage Report =====\n");
+        if self.records.is_empty() {
+            out.push_str("Nothing was decrypted.\n");
+        }
+        for party in self.parties() {
+            writeln!(out, "{} learned:", party).unwrap();
+            for record in self.records.iter().filter(|r| r.party == party) {
+                writeln!(out, "  - [{}] {} = {}", record.output, record.item, record.value).unwrap();
+            }
+        }
+        out.push_str("==========================\n");
+        out
+    }
+
+    /// JSON report listing what each party learned.
+    pub fn to_json(&self) -> String {
+        let parties: Vec<String> = self
+            .parties()
+            .into_iter()
+            .map(|party| {
+                let learned: Vec<String> = self
+                    .records
+                    .iter()
+                    .filter(|r| r.party == party)
+                    .map(|r| {
+                        format!(
+                            "{{\"output\":{},\"item\":{},\"value\":{}}}",
+                            json_string(&r.output.to_string()),
+                            json_string(&r.item),
+                            json_string(&r.value)
+                        )
+                    })
+                    .collect();
+                format!("{{\"party\":{},\"learned\":[{}]}}", json_string(party), learned.join(","))
+            })
+            .collect();
+        format!("{{\"parties\":[{}]}}", parties.join(","))
+    }
+}
+
+/// Quote and escape a string for JSON.
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
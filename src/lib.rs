@@ -1 +1,22 @@
+pub mod all_pay;
 pub mod auction;
+pub mod candle;
+pub mod clock;
+pub mod combinatorial;
+pub mod consortium;
+pub mod deadline;
+pub mod disclosure;
+pub mod double_auction;
+pub mod dutch;
+pub mod english;
+pub mod gsp;
+pub mod japanese;
+pub mod leakage;
+pub mod privacy;
+pub mod quadratic;
+pub mod raffle;
+pub mod session;
+pub mod sorting;
+pub mod statistics;
+pub mod tender;
+pub mod voting;
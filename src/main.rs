@@ -2,9 +2,9 @@
 
 use std::collections::HashSet;
 use std::io::{self, Write};
-use tfhe::prelude::*;
 use tfhe::{ConfigBuilder, generate_keys, set_server_key};
-use ZamaHEBid::auction::Auction;
+use ZamaHEBid::auction::{Auction, AuctionPhase, RoundingMode};
+use ZamaHEBid::disclosure::Disclosure;
 use crossterm::{execute, terminal::{Clear, ClearType}, cursor::MoveTo,};
 
 // Clears console on all OS
@@ -49,7 +49,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut auction = Auction::new(&client_key, min_bid);
 
-    println!("\nMinimum bid set.");
+    println!("\nMinimum bid set. Auction terms:");
+    println!("{}", auction.public_terms());
     println!("Enter 'x' at the User ID prompt to finish bidding early.\n");
 
     // ---------- Bidding Loop ----------
@@ -92,7 +93,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             continue;
         }
         // Accept and encrypt the bid.
-        auction.add_bid(&user_id, bid_value);
+        if let Err(e) = auction.add_bid(&user_id, bid_value) {
+            println!("{}", e);
+            continue;
+        }
         submitted_users.insert(user_id);
         println!("Bid accepted.\n");
         // Clear the console after a bid is entered.
@@ -106,9 +110,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // ---------- Handle Tie-breaks & Final Computation ----------
-    let (mut max_ct, mut top_bidders) = auction.compute_max_encrypted_bid();
+    let (_, mut top_bidders) = auction.compute_max_encrypted_bid()?;
+    if top_bidders.len() > 1 {
+        auction.set_phase(AuctionPhase::TieBreak);
+    }
     while top_bidders.len() > 1 {
-        let current_high: u16 = max_ct.decrypt(auction.client_key());
+        let current_high = auction.reveal_highest_bid("tied bidders")?;
+        auction.record_disclosure("public", Disclosure::WinnerIdentity, "tied bidders", &top_bidders.join(", "));
         println!("\n*** Tie detected! ***");
         print!("[");
         for (i, user) in top_bidders.iter().enumerate() {
@@ -127,8 +135,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 io::stdin().read_line(&mut input)?;
                 let bid_input = input.trim();
                 if bid_input.eq_ignore_ascii_case("x") {
-                    println!("{} has withdrawn from the tie-break.", user);
-                    auction.remove_bid(&user);
+                    match auction.withdraw_bid(&user) {
+                        Ok(()) => println!("{} has withdrawn from the tie-break.", user),
+                        Err(e) => println!("{}", e),
+                    }
                     break;
                 }
                 let new_bid: u16 = match bid_input.parse() {
@@ -147,33 +157,45 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     continue;
                 }
                 // Accept the rebid.
-                auction.add_bid(&user, new_bid);
+                if let Err(e) = auction.add_bid(&user, new_bid) {
+                    println!("{}", e);
+                    break;
+                }
                 clear_console();
                 break;
             }
         }
-        let result = auction.compute_max_encrypted_bid();
-        max_ct = result.0;
-        top_bidders = result.1;
+        top_bidders = auction.compute_max_encrypted_bid()?.1;
     }
 
+    auction.set_phase(AuctionPhase::Closed);
+
     // Final winner and average computation.
     let winner = top_bidders
         .get(0)
         .cloned()
         .unwrap_or_else(|| "<none>".to_string());
-    let highest_bid: u16 = max_ct.decrypt(auction.client_key());
-    let avg_ct = auction.compute_average_encrypted();
-    let avg_bid: u16 = avg_ct.decrypt(auction.client_key());
+    let highest_bid = auction.reveal_highest_bid("public");
+    let avg_bid = auction.reveal_average("public", RoundingMode::Floor);
 
     println!("\n===== Auction Results =====");
-    println!("Final Average Bid (rounded down): {}", avg_bid);
+    match avg_bid {
+        Ok(avg) => println!("Final Average Bid (rounded down): {}", avg),
+        Err(_) => println!("Final Average Bid: withheld"),
+    }
+    let highest = highest_bid.map_or("withheld".to_string(), |bid| bid.to_string());
     if winner != "<none>" {
-        println!("Highest Bid: {} (Winner: {})", highest_bid, winner);
+        auction.record_disclosure("public", Disclosure::WinnerIdentity, "winner", &winner);
+        println!("Highest Bid: {} (Winner: {})", highest, winner);
     } else {
-        println!("Highest Bid: {} (Unique winner not determined)", highest_bid);
+        println!("Highest Bid: {} (Unique winner not determined)", highest);
     }
     println!("===========================\n");
 
+    // ---------- Settlement: what this run leaked ----------
+    let leakage = auction.leakage_log();
+    println!("{}", leakage.to_text());
+    println!("Leakage report (JSON): {}", leakage.to_json());
+
     Ok(())
 }
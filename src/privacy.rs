@@ -0,0 +1,160 @@
+// src/privacy.rs
+
+// Differential-privacy noise for disclosed aggregates.
+//
+// Noise is drawn homomorphically from tfhe's oblivious pseudo-random generator
+// and added to the aggregate before decryption, so the exact value is never
+// decrypted. The noise follows a two-sided geometric (discrete Laplace)
+// distribution with scale sensitivity / epsilon, truncated at a number of
+// trials where the remaining probability mass is negligible. A disclosure whose
+// noise would need more than MAX_TRIALS encrypted trials is refused before any
+// budget is spent, instead of being released with too little noise.
+
+use rand::{thread_rng, Rng};
+use tfhe::{ClientKey, FheBool, FheUint16, FheUint32, Seed};
+use tfhe::prelude::*;
+use crate::auction::{Auction, AuctionError};
+use crate::disclosure::Disclosure;
+use crate::leakage::AUCTIONEER;
+
+/// Probability mass beyond which the geometric noise is truncated.
+const TRUNCATION_MASS: f64 = 1e-6;
+
+/// Upper bound on the number of trials per geometric sample.
+const MAX_TRIALS: u32 = 512;
+
+/// An epsilon budget shared by every noisy disclosure of one auction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrivacyBudget {
+    total: f64,
+    spent: f64,
+}
+
+impl PrivacyBudget {
+    /// Create a budget allowing disclosures with a combined epsilon of `total`.
+    pub fn new(total: f64) -> Self {
+        assert!(total > 0.0, "Privacy budget must be positive");
+        PrivacyBudget { total, spent: 0.0 }
+    }
+
+    /// Return the total epsilon of the budget.
+    pub fn total(&self) -> f64 {
+        self.total
+    }
+
+    /// Return the epsilon spent so far.
+    pub fn spent(&self) -> f64 {
+        self.spent
+    }
+
+    /// Return the epsilon still available.
+    pub fn remaining(&self) -> f64 {
+        (self.total - self.spent).max(0.0)
+    }
+
+    /// Deduct `epsilon` from the budget, or refuse if it would be exceeded.
+    pub(crate) fn spend(&mut self, epsilon: f64) -> Result<(), AuctionError> {
+        if epsilon > self.remaining() + f64::EPSILON {
+            return Err(AuctionError::PrivacyBudgetExhausted);
+        }
+        self.spent += epsilon;
+        Ok(())
+    }
+}
+
+/// Draw an encrypted geometric sample: the number of consecutive successes of
+/// a coin that lands heads with probability `alpha`, up to `trials`.
+fn encrypted_geometric(client_key: &ClientKey, alpha: f64, trials: u32) -> FheUint32 {
+    let mut rng = thread_rng();
+    // A uniform 16-bit value below this threshold is a success. MAX_TRIALS
+    // keeps alpha below 0.98, so the threshold never saturates.
+    let threshold = (alpha * 65536.0).round() as u16;
+
+    let mut still_running = FheBool::encrypt(true, client_key);
+    let mut count = FheUint32::encrypt(0u32, client_key);
+    for _ in 0..trials {
+        let r = FheUint16::generate_oblivious_pseudo_random(Seed(rng.gen::<u128>()));
+        still_running = &still_running & &r.lt(threshold);
+        count = count + FheUint32::cast_from(still_running.clone());
+    }
+    count
+}
+
+impl<'a> Auction<'a> {
+    /// Decrypt an aggregate with differential-privacy noise added homomorphically.
+    ///
+    /// `sensitivity` is how much one bidder can change the aggregate, and
+    /// `epsilon` is deducted from the auction's privacy budget. The result may
+    /// be negative or above the true range because of the noise. Fails with
+    /// `NoiseTooLarge`, leaving the budget untouched, if sensitivity / epsilon
+    /// needs more than `MAX_TRIALS` trials per noise sample.
+    pub(crate) fn reveal_noisy<C>(
+        &mut self,
+        what: Disclosure,
+        aggregate: &C,
+        sensitivity: u32,
+        epsilon: f64,
+    ) -> Result<i64, AuctionError>
+    where
+        C: Clone,
+        FheUint32: CastFrom<C>,
+    {
+        assert!(sensitivity > 0 && epsilon > 0.0, "Sensitivity and epsilon must be positive");
+        if !self.disclosure_policy().allows(what) {
+            return Err(AuctionError::NotDisclosed(what));
+        }
+
+        // Two-sided geometric noise: difference of two geometric samples.
+        let alpha = (-epsilon / sensitivity as f64).exp();
+        let required = if alpha < 1.0 {
+            (TRUNCATION_MASS.ln() / alpha.ln()).ceil()
+        } else {
+            f64::INFINITY
+        };
+        if required > MAX_TRIALS as f64 {
+            return Err(AuctionError::NoiseTooLarge(required.min(u32::MAX as f64) as u32));
+        }
+        let trials = (required as u32).max(1);
+        self.privacy_budget_mut()
+            .ok_or(AuctionError::PrivacyBudgetExhausted)?
+            .spend(epsilon)?;
+
+        let positive = encrypted_geometric(self.client_key(), alpha, trials);
+        let negative = encrypted_geometric(self.client_key(), alpha, trials);
+
+        // Add a public offset so the encrypted result cannot wrap below zero;
+        // it is subtracted again after decryption.
+        let value = FheUint32::cast_from(aggregate.clone());
+        let noisy = value + trials + positive - negative;
+        let item = format!("{} with noise (public offset {})", what, trials);
+        let decrypted: u32 = self.reveal_to(AUCTIONEER, what, &item, &noisy)?;
+        Ok(decrypted as i64 - trials as i64)
+    }
+
+    /// Disclose the number of bids with differential-privacy noise. The count
+    /// is encrypted and goes through `reveal_noisy` with sensitivity 1, since
+    /// one bidder changes it by at most one.
+    pub fn reveal_noisy_bid_count(&mut self, epsilon: f64) -> Result<i64, AuctionError> {
+        let count = FheUint32::encrypt(self.count_bids() as u32, self.client_key());
+        self.reveal_noisy(Disclosure::BidCount, &count, 1, epsilon)
+    }
+
+    /// Disclose the average bid (rounded down) with differential-privacy noise.
+    /// Each bid is capped obliviously at the public `bid_cap` before averaging,
+    /// so changing one bid moves the average by at most bid_cap / count, which
+    /// is the sensitivity used for the noise.
+    pub fn reveal_noisy_average(&mut self, bid_cap: u16, epsilon: f64) -> Result<i64, AuctionError> {
+        assert!(self.count_bids() > 0, "No bids to compute average");
+        let client_key = self.client_key();
+        let count = self.count_bids() as u32;
+
+        let cap = FheUint16::encrypt(bid_cap, client_key);
+        let mut sum = FheUint32::encrypt(0u32, client_key);
+        for enc_bid in self.encrypted_bids() {
+            sum = sum + FheUint32::cast_from(enc_bid.min(&cap));
+        }
+        let average = sum / count;
+        let sensitivity = (bid_cap as u32).div_ceil(count).max(1);
+        self.reveal_noisy(Disclosure::Average, &average, sensitivity, epsilon)
+    }
+}
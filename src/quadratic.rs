@@ -0,0 +1,127 @@
+// src/quadratic.rs
+
+// Quadratic voting.
+//
+// Each registered voter holds an encrypted credit balance, stored as their bid
+// in an Auction book, and spreads votes over the options. Casting v votes for
+// an option costs v² credits. The server computes each ballot's total cost
+// homomorphically and compares it with the voter's balance; ballots that cost
+// more than the balance are zeroed out obliviously, so nobody learns which
+// ballots were discarded. Only the winning option is decrypted.
+
+use tfhe::{ClientKey, FheUint16, FheUint32, FheUint64};
+use tfhe::prelude::*;
+use crate::auction::{Auction, AuctionError, AuctionPhase};
+use crate::disclosure::Disclosure;
+use crate::leakage::AUCTIONEER;
+use crate::voting::argmax;
+
+/// Encrypted result of counting the quadratic ballots.
+#[derive(Clone)]
+pub struct QuadraticTally {
+    pub votes: Vec<FheUint32>,    // Votes per option, in the order the options were given
+    pub valid_ballots: FheUint16, // Number of ballots within their voter's credits
+}
+
+pub struct QuadraticPoll<'a> {
+    book: Auction<'a>,     // Voter registry, encrypted credits and ballots
+    options: Vec<String>,  // Public list of options
+}
+
+impl<'a> QuadraticPoll<'a> {
+    /// Create a quadratic poll over the given options.
+    pub fn new(client_key: &'a ClientKey, options: &[&str]) -> Self {
+        assert!(!options.is_empty(), "A poll needs at least one option");
+        QuadraticPoll {
+            book: Auction::new(client_key, 0),
+            options: options.iter().map(|o| o.to_string()).collect(),
+        }
+    }
+
+    /// Return the options, in ballot order.
+    pub fn options(&self) -> &[String] {
+        &self.options
+    }
+
+    /// Return the underlying book (for its leakage log or disclosure policy).
+    pub fn book(&self) -> &Auction<'a> {
+        &self.book
+    }
+
+    /// Register a voter and encrypt their credit balance
+    /// (registering again replaces the balance).
+    pub fn register_voter(&mut self, user_id: &str, credits: u16) -> Result<(), AuctionError> {
+        self.book.add_bid(user_id, credits)?;
+        self.book.register_bidder(user_id);
+        Ok(())
+    }
+
+    /// Encrypt and store a voter's votes, one entry per option (a later ballot
+    /// replaces the earlier one). The cost is not checked here; a ballot that
+    /// exceeds the voter's credits is discarded obliviously when tallying.
+    pub fn cast_ballot(&mut self, user_id: &str, votes: &[u16]) -> Result<(), AuctionError> {
+        assert_eq!(votes.len(), self.options.len(), "A ballot needs one entry per option");
+        if self.book.phase() == AuctionPhase::Closed {
+            return Err(AuctionError::AuctionClosed);
+        }
+        if !self.book.is_registered(user_id) {
+            return Err(AuctionError::NotRegistered(user_id.to_string()));
+        }
+        let client_key = self.book.client_key();
+        let encrypted = votes
+            .iter()
+            .map(|&v| FheUint16::encrypt(v, client_key))
+            .collect();
+        self.book.set_encrypted_vector(user_id, encrypted);
+        Ok(())
+    }
+
+    /// Return the number of ballots cast.
+    pub fn count_ballots(&self) -> usize {
+        self.book.encrypted_vectors().len()
+    }
+
+    /// Close the poll; later ballots are refused.
+    pub fn close(&mut self) {
+        self.book.set_phase(AuctionPhase::Closed);
+    }
+
+    /// Check every ballot's quadratic cost against the voter's credits and add
+    /// the affordable ones to an encrypted per-option tally.
+    pub fn compute_tally(&self) -> QuadraticTally {
+        let client_key = self.book.client_key();
+        let zero = FheUint32::encrypt(0u32, client_key);
+        let mut votes = vec![zero.clone(); self.options.len()];
+        let mut valid_ballots = FheUint16::encrypt(0u16, client_key);
+
+        for (user, credits) in self.book.bids_by_user() {
+            let Some(ballot) = self.book.encrypted_vectors().get(user) else {
+                continue;
+            };
+
+            // 64-bit cost so squares of 16-bit votes cannot overflow.
+            let mut cost = FheUint64::encrypt(0u64, client_key);
+            for v in ballot {
+                let v = FheUint64::cast_from(v.clone());
+                cost = cost + &v * &v;
+            }
+            let valid = cost.le(&FheUint64::cast_from(credits.clone()));
+
+            for (total, v) in votes.iter_mut().zip(ballot) {
+                *total = &*total + &valid.select(&FheUint32::cast_from(v.clone()), &zero);
+            }
+            valid_ballots = valid_ballots + FheUint16::cast_from(valid);
+        }
+
+        QuadraticTally { votes, valid_ballots }
+    }
+
+    /// Tally the ballots and return the option with the most votes.
+    /// Ties go to the option listed first.
+    pub fn compute_winner(&self) -> Result<Option<String>, AuctionError> {
+        let tally = self.compute_tally();
+        let index = argmax(self.book.client_key(), &tally.votes);
+        let winner: u16 = self.book.reveal_to(AUCTIONEER, Disclosure::WinnerIdentity, "winning option", &index)?;
+        Ok(self.options.get(winner as usize).cloned())
+    }
+}
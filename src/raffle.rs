@@ -0,0 +1,69 @@
+// src/raffle.rs
+
+// Oblivious lottery (raffle).
+//
+// Each participant's ticket count is stored encrypted in an Auction book. The
+// winning ticket is drawn from tfhe's oblivious pseudo-random generator and
+// reduced modulo the encrypted total number of tickets, so the draw is never
+// visible in the clear and participants cannot predict or steer it. The server
+// seeds the generator and holds the client key, so it is trusted not to
+// decrypt the draw or redraw until a favoured participant wins. The holder of the
+// winning ticket is found by comparing the draw against encrypted prefix sums
+// of the ticket counts; only the holder's index is decrypted.
+
+use rand::{thread_rng, Rng};
+use tfhe::{ClientKey, FheUint16, FheUint32, Seed};
+use tfhe::prelude::*;
+use crate::auction::{Auction, AuctionError};
+use crate::disclosure::Disclosure;
+use crate::leakage::AUCTIONEER;
+
+pub struct Raffle<'a> {
+    tickets: Auction<'a>,   // Encrypted ticket count per participant
+}
+
+impl<'a> Raffle<'a> {
+    /// Create an empty raffle.
+    pub fn new(client_key: &'a ClientKey) -> Self {
+        Raffle { tickets: Auction::new(client_key, 0) }
+    }
+
+    /// Encrypt and store a participant's number of tickets
+    /// (a later call replaces the earlier count).
+    pub fn buy_tickets(&mut self, user_id: &str, count: u16) -> Result<(), AuctionError> {
+        self.tickets.add_bid(user_id, count)
+    }
+
+    /// Return the ticket book (for its leakage log or disclosure policy).
+    pub fn tickets(&self) -> &Auction<'a> {
+        &self.tickets
+    }
+
+    /// Draw the winning ticket and return its holder. Only the holder's index
+    /// is decrypted; the ticket counts, the total and the drawn ticket are not.
+    /// The seed comes from the server's RNG, so a dishonest server could
+    /// repeat the draw; each draw is recorded in the leakage log.
+    /// At least one ticket must have been sold. The 32-bit draw reduced modulo
+    /// the total has a bias below total / 2^32.
+    pub fn draw_winner(&self) -> Result<Option<String>, AuctionError> {
+        assert!(self.tickets.count_bids() > 0, "No participants to draw from");
+        let client_key = self.tickets.client_key();
+
+        let total = self.tickets.compute_sum_encrypted();
+        let random = FheUint32::generate_oblivious_pseudo_random(Seed(thread_rng().gen::<u128>()));
+        let winning_ticket = &random % &total;
+
+        // Participant i holds tickets [prefix_{i-1}, prefix_i). The number of
+        // prefix sums at or below the winning ticket is the holder's index.
+        let (users, entries) = self.tickets.indexed_bids();
+        let mut prefix = FheUint32::encrypt(0u32, client_key);
+        let mut holder = FheUint16::encrypt(0u16, client_key);
+        for entry in &entries {
+            prefix = prefix + FheUint32::cast_from(entry.value.clone());
+            holder = holder + FheUint16::cast_from(prefix.le(&winning_ticket));
+        }
+
+        let index: u16 = self.tickets.reveal_to(AUCTIONEER, Disclosure::WinnerIdentity, "raffle winner", &holder)?;
+        Ok(users.get(index as usize).cloned())
+    }
+}
@@ -0,0 +1,122 @@
+// src/session.rs
+
+// Multi-lot sequential auctions with encrypted per-bidder budgets.
+//
+// An AuctionSession runs several Auction instances one after another. Every
+// bidder has an encrypted remaining budget: before a lot is settled, any bid
+// above the bidder's remaining budget is zeroed obliviously, and afterwards
+// the winner's budget is debited homomorphically by the winning price.
+// Budgets are never decrypted. Settled lots are kept, so their leakage logs
+// remain available for the settlement report.
+
+use std::collections::{HashMap, VecDeque};
+use tfhe::{ClientKey, FheUint16};
+use tfhe::prelude::*;
+use crate::auction::{Auction, AuctionError};
+use crate::disclosure::Disclosure;
+use crate::leakage::AUCTIONEER;
+use crate::sorting;
+
+/// Result of one lot of a session.
+pub struct LotResult {
+    pub lot: usize,                // Position of the lot in the session, from 0
+    pub winner: Option<String>,    // None if no bid within budget beat the minimum bid
+    pub price: Option<FheUint16>,  // Encrypted winning (first) price, if the lot sold
+}
+
+pub struct AuctionSession<'a> {
+    client_key: &'a ClientKey,
+    budgets: HashMap<String, FheUint16>,  // Encrypted remaining budget per bidder
+    lots: VecDeque<Auction<'a>>,          // Lots still to be settled, in order
+    settled: Vec<Auction<'a>>,            // Lots settled so far, in order
+}
+
+impl<'a> AuctionSession<'a> {
+    /// Create an empty session.
+    pub fn new(client_key: &'a ClientKey) -> Self {
+        AuctionSession {
+            client_key,
+            budgets: HashMap::new(),
+            lots: VecDeque::new(),
+            settled: Vec::new(),
+        }
+    }
+
+    /// Encrypt and set a bidder's total budget for the session.
+    /// Bidders without a budget cannot win any lot.
+    pub fn set_budget(&mut self, user_id: &str, budget: u16) {
+        self.budgets
+            .insert(user_id.to_string(), FheUint16::encrypt(budget, self.client_key));
+    }
+
+    /// Return a bidder's encrypted remaining budget.
+    pub fn remaining_budget(&self, user_id: &str) -> Option<&FheUint16> {
+        self.budgets.get(user_id)
+    }
+
+    /// Queue a lot; lots are settled in the order they were added.
+    pub fn add_lot(&mut self, lot: Auction<'a>) {
+        self.lots.push_back(lot);
+    }
+
+    /// Return the number of lots not yet settled.
+    pub fn lots_remaining(&self) -> usize {
+        self.lots.len()
+    }
+
+    /// Return the settled lots, in order (for their leakage logs).
+    pub fn settled_lots(&self) -> &[Auction<'a>] {
+        &self.settled
+    }
+
+    /// Settle the next lot as a first-price auction within budgets.
+    /// Returns None when every lot has been settled.
+    pub fn run_next_lot(&mut self) -> Option<Result<LotResult, AuctionError>> {
+        let mut lot = self.lots.pop_front()?;
+        let index = self.settled.len();
+        let result = self.settle(&mut lot, index);
+        self.settled.push(lot);
+        Some(result)
+    }
+
+    fn settle(&mut self, lot: &mut Auction<'a>, index: usize) -> Result<LotResult, AuctionError> {
+        let no_sale = LotResult { lot: index, winner: None, price: None };
+        if lot.count_bids() == 0 {
+            return Ok(no_sale);
+        }
+
+        // Zero every bid above the bidder's remaining budget, obliviously.
+        let zero = FheUint16::encrypt(0u16, self.client_key);
+        let (users, entries) = lot.indexed_bids();
+        for (user, entry) in users.iter().zip(entries) {
+            let capped = match self.budgets.get(user) {
+                Some(budget) => entry.value.le(budget).select(&entry.value, &zero),
+                None => zero.clone(),
+            };
+            lot.replace_encrypted_bid(user, capped);
+        }
+
+        // First price: the highest remaining bid, if it beats the minimum bid.
+        let (users, mut entries) = lot.indexed_bids();
+        sorting::select_top(&mut entries, 1);
+        let top = entries.swap_remove(0);
+        let item = format!("lot {} sold", index);
+        let sold: bool = lot.reveal_to(AUCTIONEER, Disclosure::ClearingPrice, &item, &top.value.gt(lot.min_bid()))?;
+        if !sold {
+            return Ok(no_sale);
+        }
+
+        // Debit the winner through its encrypted index, so budgets stay correct
+        // even if the disclosure policy withholds the winner's identity. The
+        // capped price never exceeds the budget, so this cannot wrap.
+        for (i, user) in users.iter().enumerate() {
+            if let Some(budget) = self.budgets.get_mut(user) {
+                let debit = top.index.eq(i as u16).select(&top.value, &zero);
+                *budget = &*budget - &debit;
+            }
+        }
+
+        let winner = lot.reveal_bidder(&users, &top)?;
+        Ok(LotResult { lot: index, winner, price: Some(top.value) })
+    }
+}
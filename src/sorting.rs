@@ -0,0 +1,120 @@
+// src/sorting.rs
+
+// Oblivious sorting primitives over encrypted bids.
+//
+// Every compare-and-swap is evaluated homomorphically, so the sequence of
+// operations is fixed by the number of entries alone and reveals nothing
+// about the bid values or which entries were swapped.
+
+use tfhe::{ClientKey, FheUint16};
+use tfhe::prelude::*;
+
+/// An encrypted bid travelling together with the encrypted index of its bidder.
+#[derive(Clone)]
+pub struct EncryptedEntry {
+    pub value: FheUint16,
+    pub index: FheUint16,
+}
+
+/// Order two entries so that `hi.value >= lo.value` afterwards.
+/// Equal values are left in place, which keeps the network stable.
+pub(crate) fn compare_swap(hi: &mut EncryptedEntry, lo: &mut EncryptedEntry) {
+    let swap = lo.value.gt(&hi.value);
+    let new_hi = EncryptedEntry {
+        value: swap.select(&lo.value, &hi.value),
+        index: swap.select(&lo.index, &hi.index),
+    };
+    let new_lo = EncryptedEntry {
+        value: swap.select(&hi.value, &lo.value),
+        index: swap.select(&hi.index, &lo.index),
+    };
+    *hi = new_hi;
+    *lo = new_lo;
+}
+
+/// Compare-and-swap the entries at positions `hi` and `lo` of a slice, so that
+/// the larger value ends up at `hi` (which may come before or after `lo`).
+fn compare_swap_at(entries: &mut [EncryptedEntry], hi: usize, lo: usize) {
+    if hi < lo {
+        let (head, tail) = entries.split_at_mut(lo);
+        compare_swap(&mut head[hi], &mut tail[0]);
+    } else {
+        let (head, tail) = entries.split_at_mut(hi);
+        compare_swap(&mut tail[0], &mut head[lo]);
+    }
+}
+
+/// Partial selection network: afterwards the first `m` positions hold the `m`
+/// highest entries in descending order. The rest are left in an unspecified order.
+pub(crate) fn select_top(entries: &mut [EncryptedEntry], m: usize) {
+    let n = entries.len();
+    for i in 0..m.min(n) {
+        // Bubble the largest remaining entry down to position i.
+        for j in (i + 1..n).rev() {
+            compare_swap_at(entries, j - 1, j);
+        }
+    }
+}
+
+/// Bitonic sorting network: sorts the entries by value in descending order.
+/// The length must be a power of two (pad with placeholder entries first).
+pub(crate) fn bitonic_sort(entries: &mut [EncryptedEntry]) {
+    let n = entries.len();
+    assert!(n.is_power_of_two(), "Bitonic sort needs a power-of-two length");
+
+    let mut block = 2;
+    while block <= n {
+        let mut stride = block / 2;
+        while stride > 0 {
+            for i in 0..n {
+                let partner = i ^ stride;
+                if partner > i {
+                    // Alternate directions so that each block becomes bitonic.
+                    if i & block == 0 {
+                        compare_swap_at(entries, i, partner);
+                    } else {
+                        compare_swap_at(entries, partner, i);
+                    }
+                }
+            }
+            stride /= 2;
+        }
+        block *= 2;
+    }
+}
+
+/// Bids sorted obliviously from highest to lowest. Both the values and the
+/// bidder indices stay encrypted, so each can be decrypted selectively.
+pub struct EncryptedRanking {
+    users: Vec<String>,            // Bidder IDs, in the order their indices refer to
+    entries: Vec<EncryptedEntry>,  // Sorted entries, possibly followed by placeholders
+}
+
+impl EncryptedRanking {
+    pub(crate) fn new(users: Vec<String>, entries: Vec<EncryptedEntry>) -> Self {
+        EncryptedRanking { users, entries }
+    }
+
+    /// Return the sorted entries (placeholders, if any, have value 0 and no bidder).
+    pub fn entries(&self) -> &[EncryptedEntry] {
+        &self.entries
+    }
+
+    /// Return the encrypted bid at the given rank (0 = highest).
+    pub fn value_at(&self, rank: usize) -> &FheUint16 {
+        &self.entries[rank].value
+    }
+
+    /// Decrypt only the bidder indices, giving the bidder IDs from highest to
+    /// lowest bid. Bid values are not decrypted. Callers go through
+    /// `Auction::reveal_ranks`, which applies the disclosure policy and logs it.
+    pub(crate) fn decrypt_ranks(&self, client_key: &ClientKey) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let index: u16 = entry.index.decrypt(client_key);
+                self.users.get(index as usize).cloned()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,109 @@
+// src/statistics.rs
+
+// Encrypted descriptive statistics over the bids of an Auction.
+// Every statistic is returned as its own ciphertext, so each one can be
+// decrypted (or withheld) independently of the others.
+
+use tfhe::{FheUint16, FheUint32, FheUint64};
+use tfhe::prelude::*;
+use crate::auction::Auction;
+use crate::sorting;
+
+/// Encrypted statistics of an auction's bids.
+pub struct EncryptedStatistics {
+    pub count: usize,                 // Number of bids (public)
+    pub median: FheUint16,            // Median bid (mean of the two middle bids, rounded down, for even counts)
+    pub min: FheUint16,               // Lowest bid
+    pub max: FheUint16,               // Highest bid
+    pub range: FheUint16,             // max - min
+    pub sum: FheUint32,               // Sum of bids
+    pub sum_of_squares: FheUint64,    // Sum of squared bids, for variance
+    pub bucket_edges: Vec<u16>,       // Public lower edges of the histogram buckets
+    pub histogram: Vec<FheUint16>,    // Bids per bucket [edge_i, edge_i+1); the last bucket is open-ended
+}
+
+/// Population variance from decrypted aggregates.
+pub fn variance(sum: u32, sum_of_squares: u64, count: usize) -> f64 {
+    let n = count as f64;
+    let mean = sum as f64 / n;
+    (sum_of_squares as f64 / n - mean * mean).max(0.0)
+}
+
+/// Population standard deviation from decrypted aggregates.
+pub fn standard_deviation(sum: u32, sum_of_squares: u64, count: usize) -> f64 {
+    variance(sum, sum_of_squares, count).sqrt()
+}
+
+impl<'a> Auction<'a> {
+    /// Compute encrypted statistics of all bids. `bucket_edges` must be ascending;
+    /// bids below the first edge are not counted in any bucket.
+    pub fn compute_statistics(&self, bucket_edges: &[u16]) -> EncryptedStatistics {
+        assert!(self.count_bids() > 0, "No bids to compute statistics from");
+        assert!(
+            bucket_edges.windows(2).all(|w| w[0] < w[1]),
+            "Bucket edges must be strictly ascending"
+        );
+        let client_key = self.client_key();
+        let count = self.count_bids();
+
+        // Minimum and maximum with pairwise homomorphic min/max.
+        let mut iter = self.encrypted_bids();
+        let first = iter.next().unwrap();
+        let mut min = first.clone();
+        let mut max = first.clone();
+        for enc_bid in iter {
+            min = min.min(enc_bid);
+            max = max.max(enc_bid);
+        }
+        let range = &max - &min;
+
+        // Median via encrypted selection of the upper half.
+        let (_, mut entries) = self.indexed_bids();
+        sorting::select_top(&mut entries, count / 2 + 1);
+        let median = if count % 2 == 1 {
+            entries[count / 2].value.clone()
+        } else {
+            let upper = FheUint32::cast_from(entries[count / 2 - 1].value.clone());
+            let lower = FheUint32::cast_from(entries[count / 2].value.clone());
+            FheUint16::cast_from((upper + lower) / 2u32)
+        };
+
+        // Sum of squares in 64 bits so that it cannot overflow.
+        let mut sum_of_squares = FheUint64::encrypt(0u64, client_key);
+        for enc_bid in self.encrypted_bids() {
+            let enc_bid_64 = FheUint64::cast_from(enc_bid.clone());
+            sum_of_squares = sum_of_squares + &enc_bid_64 * &enc_bid_64;
+        }
+
+        // Histogram: count the bids at or above each public edge, then take
+        // differences of neighbouring counts.
+        let at_or_above: Vec<FheUint16> = bucket_edges
+            .iter()
+            .map(|&edge| {
+                let mut counter = FheUint16::encrypt(0u16, client_key);
+                for enc_bid in self.encrypted_bids() {
+                    counter = counter + FheUint16::cast_from(enc_bid.ge(edge));
+                }
+                counter
+            })
+            .collect();
+        let histogram = (0..at_or_above.len())
+            .map(|i| match at_or_above.get(i + 1) {
+                Some(next) => &at_or_above[i] - next,
+                None => at_or_above[i].clone(),
+            })
+            .collect();
+
+        EncryptedStatistics {
+            count,
+            median,
+            min,
+            max,
+            range,
+            sum: self.compute_sum_encrypted(),
+            sum_of_squares,
+            bucket_edges: bucket_edges.to_vec(),
+            histogram,
+        }
+    }
+}
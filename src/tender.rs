@@ -0,0 +1,108 @@
+// src/tender.rs
+
+// Multi-attribute procurement tenders.
+//
+// Each supplier's bid carries several encrypted attributes (for example price,
+// delivery days and warranty years). The server scores every bid as a
+// weighted sum of its attributes with public weights, using only scalar
+// multiplications and additions, and picks the best score by encrypted
+// argmax. Only the winning supplier (and, on request, its score) is decrypted.
+
+use tfhe::{FheUint16, FheUint64};
+use tfhe::prelude::*;
+use crate::auction::{Auction, AuctionError};
+use crate::disclosure::Disclosure;
+use crate::leakage::AUCTIONEER;
+
+/// Whether a larger or a smaller attribute value is better.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Criterion {
+    /// Larger is better (e.g. warranty years). Scores `value`.
+    HigherIsBetter,
+    /// Smaller is better (e.g. price). Scores `worst - value`, and values at or
+    /// above `worst` score nothing.
+    LowerIsBetter { worst: u16 },
+}
+
+/// Public weight of one attribute in the score.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AttributeWeight {
+    pub weight: u16,
+    pub criterion: Criterion,
+}
+
+/// Outcome of a tender.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TenderOutcome {
+    pub winner: Option<String>,  // Best-scoring supplier
+    pub score: Option<u64>,      // Winning score, if it was requested
+}
+
+impl<'a> Auction<'a> {
+    /// Encrypt and store a supplier's tender attributes, in the same order as
+    /// the weights later passed to `compute_tender_winner`.
+    pub fn add_tender_bid(&mut self, user_id: &str, attributes: &[u16]) -> Result<(), AuctionError> {
+        if self.close_if_expired() {
+            return Err(AuctionError::AuctionClosed);
+        }
+        let client_key = self.client_key();
+        let encrypted = attributes
+            .iter()
+            .map(|&value| FheUint16::encrypt(value, client_key))
+            .collect();
+        self.set_encrypted_vector(user_id, encrypted);
+        Ok(())
+    }
+
+    /// Score every tender with the public weights and pick the best one.
+    /// Ties go to the supplier whose ID sorts first. Scores are 64 bits wide,
+    /// so a sum of 16-bit value × 16-bit weight terms cannot wrap.
+    pub fn compute_tender_winner(
+        &self,
+        weights: &[AttributeWeight],
+        reveal_score: bool,
+    ) -> Result<TenderOutcome, AuctionError> {
+        let tenders = self.encrypted_vectors();
+        assert!(!tenders.is_empty(), "No tenders to score");
+        let client_key = self.client_key();
+
+        let mut suppliers: Vec<&String> = tenders.keys().collect();
+        suppliers.sort();
+
+        let mut best_score = FheUint64::encrypt(0u64, client_key);
+        let mut best_index = FheUint16::encrypt(0u16, client_key);
+        for (index, supplier) in suppliers.iter().enumerate() {
+            let attributes = &tenders[*supplier];
+            assert_eq!(attributes.len(), weights.len(), "Every tender needs one value per weight");
+
+            // Weighted sum of the attribute terms.
+            let mut score = FheUint64::encrypt(0u64, client_key);
+            for (value, weight) in attributes.iter().zip(weights) {
+                let term = match weight.criterion {
+                    Criterion::HigherIsBetter => value.clone(),
+                    Criterion::LowerIsBetter { worst } => {
+                        let enc_worst = FheUint16::encrypt(worst, client_key);
+                        &enc_worst - &value.min(&enc_worst)
+                    }
+                };
+                score = score + FheUint64::cast_from(term) * weight.weight as u64;
+            }
+
+            // Encrypted argmax; index 0 is the starting best.
+            let better = score.gt(&best_score);
+            best_score = better.select(&score, &best_score);
+            best_index = better.select(&FheUint16::encrypt(index as u16, client_key), &best_index);
+        }
+
+        let index: u16 = self.reveal_to(AUCTIONEER, Disclosure::WinnerIdentity, "winning tender", &best_index)?;
+        let score = if reveal_score {
+            Some(self.reveal_to(AUCTIONEER, Disclosure::ClearingPrice, "winning tender score", &best_score)?)
+        } else {
+            None
+        };
+        Ok(TenderOutcome {
+            winner: suppliers.get(index as usize).map(|s| s.to_string()),
+            score,
+        })
+    }
+}
@@ -0,0 +1,143 @@
+// src/voting.rs
+
+// Sealed-ballot polls.
+//
+// A poll reuses an Auction book for key handling, the voter registry, ballot
+// storage and the leakage log. Each ballot is a one-hot vector of encrypted
+// 0/1 entries, one per option. The server checks homomorphically that every
+// entry is at most 1 and that the entries sum to exactly 1; invalid ballots are
+// zeroed out obliviously before tallying. Only the winning option is
+// decrypted, never an individual ballot.
+
+use tfhe::{ClientKey, FheBool, FheUint16};
+use tfhe::prelude::*;
+use crate::auction::{Auction, AuctionError, AuctionPhase};
+use crate::disclosure::Disclosure;
+use crate::leakage::AUCTIONEER;
+
+/// Encrypted result of counting the ballots.
+#[derive(Clone)]
+pub struct EncryptedTally {
+    pub counts: Vec<FheUint16>,   // Votes per option, in the order the options were given
+    pub valid_ballots: FheUint16, // Number of ballots that passed the validity check
+}
+
+pub struct Poll<'a> {
+    book: Auction<'a>,     // Voter registry and encrypted ballots
+    options: Vec<String>,  // Public list of options
+}
+
+impl<'a> Poll<'a> {
+    /// Create a poll over the given options.
+    pub fn new(client_key: &'a ClientKey, options: &[&str]) -> Self {
+        assert!(!options.is_empty(), "A poll needs at least one option");
+        Poll {
+            book: Auction::new(client_key, 0),
+            options: options.iter().map(|o| o.to_string()).collect(),
+        }
+    }
+
+    /// Return the options, in ballot order.
+    pub fn options(&self) -> &[String] {
+        &self.options
+    }
+
+    /// Return the underlying book (for its leakage log or disclosure policy).
+    pub fn book(&self) -> &Auction<'a> {
+        &self.book
+    }
+
+    /// Allow a voter to cast a ballot. Returns false if they were already registered.
+    pub fn register_voter(&mut self, user_id: &str) -> bool {
+        self.book.register_bidder(user_id)
+    }
+
+    /// Encrypt and store a one-hot ballot for the option at `choice`.
+    pub fn cast_vote(&mut self, user_id: &str, choice: usize) -> Result<(), AuctionError> {
+        assert!(choice < self.options.len(), "Choice is not one of the options");
+        let ballot: Vec<u16> = (0..self.options.len()).map(|i| (i == choice) as u16).collect();
+        self.cast_ballot(user_id, &ballot)
+    }
+
+    /// Encrypt and store a raw ballot with one entry per option (a later ballot
+    /// replaces the earlier one). The ballot is not checked here; a ballot that
+    /// is not one-hot is discarded obliviously when the votes are tallied.
+    pub fn cast_ballot(&mut self, user_id: &str, ballot: &[u16]) -> Result<(), AuctionError> {
+        assert_eq!(ballot.len(), self.options.len(), "A ballot needs one entry per option");
+        if self.book.phase() == AuctionPhase::Closed {
+            return Err(AuctionError::AuctionClosed);
+        }
+        if !self.book.is_registered(user_id) {
+            return Err(AuctionError::NotRegistered(user_id.to_string()));
+        }
+        let client_key = self.book.client_key();
+        let encrypted = ballot
+            .iter()
+            .map(|&entry| FheUint16::encrypt(entry, client_key))
+            .collect();
+        self.book.set_encrypted_vector(user_id, encrypted);
+        Ok(())
+    }
+
+    /// Return the number of ballots cast.
+    pub fn count_ballots(&self) -> usize {
+        self.book.encrypted_vectors().len()
+    }
+
+    /// Close the poll; later ballots are refused.
+    pub fn close(&mut self) {
+        self.book.set_phase(AuctionPhase::Closed);
+    }
+
+    /// Check every ballot and add the valid ones to an encrypted per-option tally.
+    pub fn compute_tally(&self) -> EncryptedTally {
+        let client_key = self.book.client_key();
+        let zero = FheUint16::encrypt(0u16, client_key);
+        let mut counts = vec![zero.clone(); self.options.len()];
+        let mut valid_ballots = zero.clone();
+
+        for ballot in self.book.encrypted_vectors().values() {
+            // One-hot: every entry is 0 or 1, and exactly one entry is 1.
+            let mut valid = FheBool::encrypt(true, client_key);
+            let mut sum = zero.clone();
+            for entry in ballot {
+                valid = &valid & &entry.le(1u16);
+                sum = sum + entry;
+            }
+            valid = &valid & &sum.eq(1u16);
+
+            for (count, entry) in counts.iter_mut().zip(ballot) {
+                *count = &*count + &valid.select(entry, &zero);
+            }
+            valid_ballots = valid_ballots + FheUint16::cast_from(valid);
+        }
+
+        EncryptedTally { counts, valid_ballots }
+    }
+
+    /// Tally the ballots and return the option with the most votes.
+    /// Ties go to the option listed first.
+    pub fn compute_winner(&self) -> Result<Option<String>, AuctionError> {
+        let tally = self.compute_tally();
+        let index = argmax(self.book.client_key(), &tally.counts);
+        let winner: u16 = self.book.reveal_to(AUCTIONEER, Disclosure::WinnerIdentity, "winning option", &index)?;
+        Ok(self.options.get(winner as usize).cloned())
+    }
+}
+
+/// Encrypted index of the largest count (the first one on ties), for counts of
+/// any encrypted integer width.
+pub(crate) fn argmax<T>(client_key: &ClientKey, counts: &[T]) -> FheUint16
+where
+    T: Clone + for<'b> FheOrd<&'b T, Output = FheBool>,
+    FheBool: IfThenElse<T>,
+{
+    let mut best = counts[0].clone();
+    let mut best_index = FheUint16::encrypt(0u16, client_key);
+    for (index, count) in counts.iter().enumerate().skip(1) {
+        let better = count.gt(&best);
+        best = better.select(count, &best);
+        best_index = better.select(&FheUint16::encrypt(index as u16, client_key), &best_index);
+    }
+    best_index
+}
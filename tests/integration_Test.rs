@@ -1,6 +1,13 @@
-use tfhe::{ConfigBuilder, generate_keys, set_server_key};
+use tfhe::{ConfigBuilder, generate_keys, set_server_key, PublicKey};
 use tfhe::prelude::*;
-use ZamaHEBid::auction::Auction;
+use ZamaHEBid::auction::{Auction, RoundingMode};
+use ZamaHEBid::combinatorial::CombinatorialAuction;
+use ZamaHEBid::double_auction::DoubleAuction;
+use ZamaHEBid::leakage::AUCTIONEER;
+use ZamaHEBid::quadratic::QuadraticPoll;
+use ZamaHEBid::session::AuctionSession;
+use ZamaHEBid::tender::{AttributeWeight, Criterion};
+use ZamaHEBid::voting::Poll;
 
 #[test]
 fn no_tie() {
@@ -13,10 +20,10 @@ fn no_tie() {
     set_server_key(server_key);
 
     let mut auction = Auction::new(&client_key, 100);
-    auction.add_bid("User1", 200);
-    auction.add_bid("User2", 300);
+    auction.add_bid("User1", 200).unwrap();
+    auction.add_bid("User2", 300).unwrap();
 
-    let (enc_max, top_users) = auction.compute_max_encrypted_bid();
+    let (enc_max, top_users) = auction.compute_max_encrypted_bid().unwrap();
     let max_value: u16 = enc_max.decrypt(&client_key);
     let avg_value: u16 = auction.compute_average_encrypted().decrypt(&client_key);
 
@@ -43,19 +50,19 @@ fn tie() {
     set_server_key(server_key);
 
     let mut auction = Auction::new(&client_key, 100);
-    auction.add_bid("User1", 100);
-    auction.add_bid("User2", 250);
-    auction.add_bid("User3", 250);
+    auction.add_bid("User1", 100).unwrap();
+    auction.add_bid("User2", 250).unwrap();
+    auction.add_bid("User3", 250).unwrap();
 
     println!("Initial Bids: [User1: 100, User2: 250, User3: 250]");
     println!("Tie detected between User2 and User3. Starting tie-break round...");
 
-    auction.add_bid("User2", 300);
-    auction.add_bid("User3", 325);
+    auction.add_bid("User2", 300).unwrap();
+    auction.add_bid("User3", 325).unwrap();
 
     println!("After tie-break: [User2: 300, User3: 325]");
 
-    let (enc_max, top_users) = auction.compute_max_encrypted_bid();
+    let (enc_max, top_users) = auction.compute_max_encrypted_bid().unwrap();
     let max_value: u16 = enc_max.decrypt(&client_key);
     let avg_value: u16 = auction.compute_average_encrypted().decrypt(&client_key);
 
@@ -81,28 +88,28 @@ fn all_bidders_tie() {
     set_server_key(server_key);
 
     let mut auction = Auction::new(&client_key, 100);
-    auction.add_bid("User1", 300);
-    auction.add_bid("User2", 300);
-    auction.add_bid("User3", 300);
-    auction.add_bid("User4", 300);
+    auction.add_bid("User1", 300).unwrap();
+    auction.add_bid("User2", 300).unwrap();
+    auction.add_bid("User3", 300).unwrap();
+    auction.add_bid("User4", 300).unwrap();
 
     println!("Initial Bids: [User1: 300, User2: 300, User3: 300, User4: 300]");
     println!("Tie detected among all users. Starting first tie-break round...");
 
-    auction.add_bid("User1", 350);
-    auction.add_bid("User2", 350);
-    auction.add_bid("User3", 400);
-    auction.add_bid("User4", 400);
+    auction.add_bid("User1", 350).unwrap();
+    auction.add_bid("User2", 350).unwrap();
+    auction.add_bid("User3", 400).unwrap();
+    auction.add_bid("User4", 400).unwrap();
 
     println!("After first tie-break: [User1: 350, User2: 350, User3: 400, User4: 400]");
     println!("Tie detected between User3 and User4. Starting second tie-break round...");
 
-    auction.add_bid("User3", 450);
-    auction.add_bid("User4", 425);
+    auction.add_bid("User3", 450).unwrap();
+    auction.add_bid("User4", 425).unwrap();
 
     println!("After second tie-break: [User3: 450, User4: 425]");
 
-    let (enc_max, top_users) = auction.compute_max_encrypted_bid();
+    let (enc_max, top_users) = auction.compute_max_encrypted_bid().unwrap();
     let max_value: u16 = enc_max.decrypt(&client_key);
     let avg_value: u16 = auction.compute_average_encrypted().decrypt(&client_key);
 
@@ -153,3 +160,338 @@ fn no_bids() {
     println!("\n✅ Completed No Bids Scenario");
     println!("----------------------------------------------\n");
 }
+
+#[test]
+fn average_rounding_modes() {
+    println!("\n\n==============================================");
+    println!("        Starting Average Rounding Modes        ");
+    println!("==============================================\n");
+
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+    set_server_key(server_key);
+
+    // Same final bids as the tie scenario: 725 / 3 = 241.67
+    let mut auction = Auction::new(&client_key, 50);
+    auction.add_bid("User1", 100).unwrap();
+    auction.add_bid("User2", 300).unwrap();
+    auction.add_bid("User3", 325).unwrap();
+
+    let floor = auction.compute_average_with(RoundingMode::Floor);
+    let sum: u32 = floor.sum.decrypt(&client_key);
+    let floor_avg: u16 = floor.average.decrypt(&client_key);
+    let nearest: u16 = auction.compute_average_with(RoundingMode::Nearest).average.decrypt(&client_key);
+    let ceiling: u16 = auction.compute_average_with(RoundingMode::Ceiling).average.decrypt(&client_key);
+
+    println!("Sum: {} over {} bids", sum, floor.count);
+    println!("Floor: {}, Nearest: {}, Ceiling: {}", floor_avg, nearest, ceiling);
+
+    assert_eq!(sum, 725);
+    assert_eq!(floor.count, 3);
+    assert_eq!(floor_avg, 241);
+    assert_eq!(nearest, 242);
+    assert_eq!(ceiling, 242);
+
+    println!("\n✅ Completed Average Rounding Modes");
+    println!("----------------------------------------------\n");
+}
+
+#[test]
+fn double_auction_clearing() {
+    println!("\n\n==============================================");
+    println!("         Starting Double Auction Scenario       ");
+    println!("==============================================\n");
+
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+    set_server_key(server_key);
+
+    let mut exchange = DoubleAuction::new(&client_key);
+    exchange.add_buy_bid("Buyer1", 300).unwrap();
+    exchange.add_buy_bid("Buyer2", 220).unwrap();
+    exchange.add_buy_bid("Buyer3", 150).unwrap();
+    exchange.add_sell_ask("Seller1", 100).unwrap();
+    exchange.add_sell_ask("Seller2", 200).unwrap();
+    exchange.add_sell_ask("Seller3", 260).unwrap();
+
+    println!("Bids: [300, 220, 150], Asks: [100, 200, 260]");
+
+    let outcome = exchange.compute_clearing().unwrap();
+    let price: u16 = outcome.price.as_ref().unwrap().decrypt(&client_key);
+
+    println!("Trades: {}", outcome.trades);
+    println!("Matches: {:?}", outcome.matches);
+    println!("Clearing price: {}", price);
+
+    // 300 >= 100 and 220 >= 200 trade, 150 < 260 does not; price (220 + 200) / 2
+    assert_eq!(outcome.trades, 2);
+    assert_eq!(
+        outcome.matches,
+        vec![
+            ("Buyer1".to_string(), "Seller1".to_string()),
+            ("Buyer2".to_string(), "Seller2".to_string()),
+        ]
+    );
+    assert_eq!(price, 210);
+
+    println!("\n✅ Completed Double Auction Scenario");
+    println!("----------------------------------------------\n");
+}
+
+#[test]
+fn combinatorial_bundles() {
+    println!("\n\n==============================================");
+    println!("       Starting Combinatorial Bundle Scenario   ");
+    println!("==============================================\n");
+
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+    set_server_key(server_key);
+
+    let mut auction = CombinatorialAuction::new(&client_key, &["A", "B", "C"]);
+    auction.add_bundle_bid("User1", &["A", "B"], 300).unwrap();
+    auction.add_bundle_bid("User2", &["A"], 120).unwrap();
+    auction.add_bundle_bid("User3", &["B"], 150).unwrap();
+    auction.add_bundle_bid("User3", &["C"], 90).unwrap();
+    assert!(auction.add_bundle_bid("User2", &["D"], 50).is_err());
+
+    println!("Bids: User1 {{A,B}}: 300, User2 {{A}}: 120, User3 {{B}}: 150, User3 {{C}}: 90");
+
+    let outcome = auction.compute_best_allocation().unwrap();
+
+    println!("Winning allocation: {:?}", outcome.allocation);
+    println!("Revenue: {}", outcome.revenue);
+    println!("{}", auction.book().leakage_log().to_text());
+
+    // {A,B} to User1 plus {C} to User3 (390) beats {A} + {B} (270)
+    assert_eq!(outcome.revenue, 390);
+    assert_eq!(
+        outcome.allocation,
+        vec![
+            ("User1".to_string(), vec!["A".to_string(), "B".to_string()]),
+            ("User3".to_string(), vec!["C".to_string()]),
+        ]
+    );
+    assert_eq!(auction.book().leakage_log().records().len(), 2);
+
+    println!("\n✅ Completed Combinatorial Bundle Scenario");
+    println!("----------------------------------------------\n");
+}
+
+#[test]
+fn session_with_budgets() {
+    println!("\n\n==============================================");
+    println!("        Starting Budgeted Session Scenario      ");
+    println!("==============================================\n");
+
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+    set_server_key(server_key);
+
+    let mut session = AuctionSession::new(&client_key);
+    session.set_budget("User1", 500);
+    session.set_budget("User2", 350);
+
+    let mut lot1 = Auction::new(&client_key, 10);
+    lot1.add_bid("User1", 400).unwrap();
+    lot1.add_bid("User2", 300).unwrap();
+    let mut lot2 = Auction::new(&client_key, 10);
+    lot2.add_bid("User1", 200).unwrap(); // exceeds User1's remaining 100, zeroed
+    lot2.add_bid("User2", 150).unwrap();
+    session.add_lot(lot1);
+    session.add_lot(lot2);
+
+    println!("Budgets: User1 500, User2 350");
+    println!("Lot 1 bids: [User1: 400, User2: 300], Lot 2 bids: [User1: 200, User2: 150]");
+
+    let first = session.run_next_lot().unwrap().unwrap();
+    let second = session.run_next_lot().unwrap().unwrap();
+    let first_price: u16 = first.price.as_ref().unwrap().decrypt(&client_key);
+    let second_price: u16 = second.price.as_ref().unwrap().decrypt(&client_key);
+    let user1_left: u16 = session.remaining_budget("User1").unwrap().decrypt(&client_key);
+    let user2_left: u16 = session.remaining_budget("User2").unwrap().decrypt(&client_key);
+
+    println!("Lot 1: {:?} at {}", first.winner, first_price);
+    println!("Lot 2: {:?} at {}", second.winner, second_price);
+    println!("Remaining budgets: User1 {}, User2 {}", user1_left, user2_left);
+
+    assert_eq!(first.winner, Some("User1".to_string()));
+    assert_eq!(first_price, 400);
+    assert_eq!(second.winner, Some("User2".to_string()));
+    assert_eq!(second_price, 150);
+    assert_eq!(user1_left, 100);
+    assert_eq!(user2_left, 200);
+    assert!(session.run_next_lot().is_none());
+    // Each settled lot keeps its own leakage log ("sold" bit and winner index).
+    assert_eq!(session.settled_lots().len(), 2);
+    for lot in session.settled_lots() {
+        println!("{}", lot.leakage_log().to_text());
+        assert_eq!(lot.leakage_log().records().len(), 2);
+    }
+
+    println!("\n✅ Completed Budgeted Session Scenario");
+    println!("----------------------------------------------\n");
+}
+
+#[test]
+fn weighted_tender() {
+    println!("\n\n==============================================");
+    println!("          Starting Weighted Tender Scenario     ");
+    println!("==============================================\n");
+
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+    set_server_key(server_key);
+
+    // Attributes: [price, delivery days, warranty years]
+    let weights = [
+        AttributeWeight { weight: 3, criterion: Criterion::LowerIsBetter { worst: 1000 } },
+        AttributeWeight { weight: 5, criterion: Criterion::LowerIsBetter { worst: 60 } },
+        AttributeWeight { weight: 20, criterion: Criterion::HigherIsBetter },
+    ];
+    let mut tender = Auction::new(&client_key, 0);
+    tender.add_tender_bid("Supplier1", &[800, 10, 2]).unwrap();
+    tender.add_tender_bid("Supplier2", &[700, 40, 1]).unwrap();
+    tender.add_tender_bid("Supplier3", &[900, 5, 5]).unwrap();
+
+    println!("Tenders: Supplier1 [800, 10, 2], Supplier2 [700, 40, 1], Supplier3 [900, 5, 5]");
+
+    let outcome = tender.compute_tender_winner(&weights, true).unwrap();
+
+    println!("Winner: {:?}", outcome.winner);
+    println!("Score: {:?}", outcome.score);
+
+    // Scores: 890, 1020, 675
+    assert_eq!(outcome.winner, Some("Supplier2".to_string()));
+    assert_eq!(outcome.score, Some(1020));
+
+    println!("\n✅ Completed Weighted Tender Scenario");
+    println!("----------------------------------------------\n");
+}
+
+#[test]
+fn sealed_poll() {
+    println!("\n\n==============================================");
+    println!("          Starting Sealed Poll Scenario         ");
+    println!("==============================================\n");
+
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+    set_server_key(server_key);
+
+    let mut poll = Poll::new(&client_key, &["Red", "Green", "Blue"]);
+    for voter in ["Voter1", "Voter2", "Voter3", "Voter4", "Voter5"] {
+        poll.register_voter(voter);
+    }
+    poll.cast_vote("Voter1", 1).unwrap();
+    poll.cast_vote("Voter2", 2).unwrap();
+    poll.cast_vote("Voter3", 1).unwrap();
+    // Invalid ballots: two votes at once, and a weighted vote.
+    poll.cast_ballot("Voter4", &[0, 1, 1]).unwrap();
+    poll.cast_ballot("Voter5", &[0, 0, 3]).unwrap();
+
+    println!("Ballots: Voter1 Green, Voter2 Blue, Voter3 Green, Voter4 [0, 1, 1], Voter5 [0, 0, 3]");
+
+    let tally = poll.compute_tally();
+    let counts: Vec<u16> = tally.counts.iter().map(|c| c.decrypt(&client_key)).collect();
+    let valid: u16 = tally.valid_ballots.decrypt(&client_key);
+    let winner = poll.compute_winner().unwrap();
+
+    println!("Tally: {:?}, valid ballots: {}", counts, valid);
+    println!("Winner: {:?}", winner);
+
+    assert_eq!(counts, vec![0, 2, 1]);
+    assert_eq!(valid, 3);
+    assert_eq!(winner, Some("Green".to_string()));
+
+    println!("\n✅ Completed Sealed Poll Scenario");
+    println!("----------------------------------------------\n");
+}
+
+#[test]
+fn quadratic_voting() {
+    println!("\n\n==============================================");
+    println!("        Starting Quadratic Voting Scenario      ");
+    println!("==============================================\n");
+
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+    set_server_key(server_key);
+
+    let mut poll = QuadraticPoll::new(&client_key, &["ProjectA", "ProjectB", "ProjectC"]);
+    poll.register_voter("Voter1", 10).unwrap();
+    poll.register_voter("Voter2", 9).unwrap();
+    poll.register_voter("Voter3", 4).unwrap();
+    poll.register_voter("Voter4", 5).unwrap();
+    poll.cast_ballot("Voter1", &[3, 1, 0]).unwrap();
+    poll.cast_ballot("Voter2", &[0, 3, 0]).unwrap();
+    // Costs 9 credits but Voter3 only has 4: discarded.
+    poll.cast_ballot("Voter3", &[0, 0, 3]).unwrap();
+    poll.cast_ballot("Voter4", &[0, 2, 1]).unwrap();
+
+    println!("Credits: [Voter1: 10, Voter2: 9, Voter3: 4, Voter4: 5]");
+    println!("Ballots: Voter1 [3, 1, 0], Voter2 [0, 3, 0], Voter3 [0, 0, 3], Voter4 [0, 2, 1]");
+
+    let tally = poll.compute_tally();
+    let votes: Vec<u32> = tally.votes.iter().map(|v| v.decrypt(&client_key)).collect();
+    let valid: u16 = tally.valid_ballots.decrypt(&client_key);
+    let winner = poll.compute_winner().unwrap();
+
+    println!("Tally: {:?}, valid ballots: {}", votes, valid);
+    println!("Winner: {:?}", winner);
+
+    assert_eq!(votes, vec![3, 6, 1]);
+    assert_eq!(valid, 3);
+    assert_eq!(winner, Some("ProjectB".to_string()));
+
+    println!("\n✅ Completed Quadratic Voting Scenario");
+    println!("----------------------------------------------\n");
+}
+
+#[test]
+fn consortium_bid() {
+    println!("\n\n==============================================");
+    println!("         Starting Consortium Bid Scenario       ");
+    println!("==============================================\n");
+
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+    set_server_key(server_key);
+
+    let mut auction = Auction::new(&client_key, 100);
+    auction.register_consortium("Partners", &["Alpha", "Beta"]).unwrap();
+    auction.add_consortium_share("Partners", "Alpha", 300).unwrap();
+    auction.add_consortium_share("Partners", "Beta", 250).unwrap();
+    auction.submit_consortium_bid("Partners").unwrap();
+    auction.add_bid("Solo", 500).unwrap();
+    assert!(auction.add_consortium_share("Partners", "Solo", 100).is_err());
+
+    // Each member keeps their own key pair; the auction only gets the public keys.
+    let (alpha_key, _) = generate_keys(ConfigBuilder::default().build());
+    let (beta_key, _) = generate_keys(ConfigBuilder::default().build());
+    auction.register_bidder_key("Alpha", PublicKey::new(&alpha_key));
+    auction.register_bidder_key("Beta", PublicKey::new(&beta_key));
+
+    println!("Bids: Partners [Alpha: 300, Beta: 250], Solo 500");
+
+    let (max_bid, winners) = auction.compute_max_encrypted_bid().unwrap();
+    let max_value: u16 = max_bid.decrypt(&client_key);
+    let alpha: u16 = auction.reveal_contribution("Partners", "Alpha").unwrap().decrypt(&alpha_key);
+    let beta: u16 = auction.reveal_contribution("Partners", "Beta").unwrap().decrypt(&beta_key);
+
+    println!("Winner(s): {:?} with {}", winners, max_value);
+    println!("Contributions: Alpha {}, Beta {}", alpha, beta);
+
+    assert_eq!(winners, vec!["Partners".to_string()]);
+    assert_eq!(max_value, 550);
+    assert_eq!((alpha, beta), (300, 250));
+    // The auctioneer saw each share while re-encrypting it; the log says so
+    // without recording the values.
+    let log = auction.leakage_log();
+    let shares: Vec<_> = log.records().iter().filter(|r| r.item.contains("contribution")).collect();
+    assert_eq!(shares.len(), 2);
+    assert!(shares.iter().all(|r| r.party == AUCTIONEER && r.value.starts_with("withheld")));
+
+    println!("\n✅ Completed Consortium Bid Scenario");
+    println!("----------------------------------------------\n");
+}
@@ -25,13 +25,13 @@ fn performance_test_benchmark() {
     let t1 = Instant::now();
     for i in 0..n {
         let bid: u16 = rng.gen_range(1_000..=50_000);
-        auction.add_bid(&format!("BIDDER{}", i), bid);
+        auction.add_bid(&format!("BIDDER{}", i), bid).unwrap();
     }
     let enc_ms = t1.elapsed().as_millis();
 
 
     let t2 = Instant::now();
-    let (max_ct, _) = auction.compute_max_encrypted_bid();
+    let (max_ct, _) = auction.compute_max_encrypted_bid().unwrap();
     let avg_ct    = auction.compute_average_encrypted();
     let comp_ms   = t2.elapsed().as_millis();
 
@@ -1,6 +1,13 @@
-use tfhe::{ConfigBuilder, generate_keys, set_server_key, ClientKey};
+use tfhe::{ConfigBuilder, generate_keys, set_server_key, ClientKey, PublicKey};
 use tfhe::prelude::*;
-use ZamaHEBid::auction::Auction;
+use ZamaHEBid::clock::MockClock;
+use ZamaHEBid::deadline::SoftClose;
+use ZamaHEBid::disclosure::{Disclosure, DisclosurePolicy};
+use ZamaHEBid::leakage::AUCTIONEER;
+use ZamaHEBid::raffle::Raffle;
+use ZamaHEBid::statistics;
+use ZamaHEBid::voting::Poll;
+use ZamaHEBid::auction::{AmendmentPolicy, Auction, AuctionError, BuyItNow, AuctionPhase, LedgerEntry, RoundingMode, UniformPriceRule};
 
 fn setup_fhe() -> ClientKey {
     let config = ConfigBuilder::default().build();
@@ -13,10 +20,10 @@ fn setup_fhe() -> ClientKey {
 fn two_different_comparison() {
     let ck = setup_fhe();
     let mut auction = Auction::new(&ck, 0);
-    auction.add_bid("A", 200);
-    auction.add_bid("B", 100);
+    auction.add_bid("A", 200).unwrap();
+    auction.add_bid("B", 100).unwrap();
 
-    let (enc_max, winners) = auction.compute_max_encrypted_bid();
+    let (enc_max, winners) = auction.compute_max_encrypted_bid().unwrap();
     let max: u16 = enc_max.decrypt(&ck);
 
     println!("Test two_different_comparison: bids [200,100], max = {}, winners = {:?}", max, winners);
@@ -28,10 +35,10 @@ fn two_different_comparison() {
 fn two_equal_comparison() {
     let ck = setup_fhe();
     let mut auction = Auction::new(&ck, 0);
-    auction.add_bid("X", 300);
-    auction.add_bid("Y", 300);
+    auction.add_bid("X", 300).unwrap();
+    auction.add_bid("Y", 300).unwrap();
 
-    let (enc_max, winners) = auction.compute_max_encrypted_bid();
+    let (enc_max, winners) = auction.compute_max_encrypted_bid().unwrap();
     let max: u16 = enc_max.decrypt(&ck);
 
     println!("Test two_equal_comparison: bids [300,300], max = {}, winners = {:?}", max, winners);
@@ -45,10 +52,10 @@ fn list_compare() {
     let mut auction = Auction::new(&ck, 0);
     let vals = [100, 400, 250, 150];
     for (i, &v) in vals.iter().enumerate() {
-        auction.add_bid(&format!("U{}", i), v);
+        auction.add_bid(&format!("U{}", i), v).unwrap();
     }
 
-    let (enc_max, winners) = auction.compute_max_encrypted_bid();
+    let (enc_max, winners) = auction.compute_max_encrypted_bid().unwrap();
     let max: u16 = enc_max.decrypt(&ck);
 
     println!("Test list_compare: bids {:?}, max = {}, winners = {:?}", vals, max, winners);
@@ -62,7 +69,7 @@ fn list_average() {
     let mut auction = Auction::new(&ck, 0);
     let vals = [100, 250, 200, 350];
     for (i, &v) in vals.iter().enumerate() {
-        auction.add_bid(&format!("V{}", i), v);
+        auction.add_bid(&format!("V{}", i), v).unwrap();
     }
 
     let cavg = auction.compute_average_encrypted();
@@ -77,7 +84,7 @@ fn list_average() {
 fn single_value_average() {
     let ck = setup_fhe();
     let mut auction = Auction::new(&ck, 0);
-    auction.add_bid("Solo", 200);
+    auction.add_bid("Solo", 200).unwrap();
 
     let cavg = auction.compute_average_encrypted();
     let avg: u16 = cavg.decrypt(&ck);
@@ -85,3 +92,535 @@ fn single_value_average() {
     println!("Test single_value_average: bids [200], avg = {}", avg);
     assert_eq!(avg, 200);
 }
+
+#[test]
+fn increase_only_amendment() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0).with_amendment_policy(AmendmentPolicy::IncreaseOnly);
+    auction.add_bid("A", 200).unwrap();
+    auction.add_bid("A", 150).unwrap(); // lower amendment is obliviously ignored
+    auction.add_bid("A", 260).unwrap();
+
+    let history: Vec<u16> = auction.bid_history("A").iter().map(|b| b.value.decrypt(&ck)).collect();
+    let submitted: Vec<u16> = auction.bid_history("A").iter().map(|b| b.submitted.decrypt(&ck)).collect();
+    let (enc_max, _) = auction.compute_max_encrypted_bid().unwrap();
+    let max: u16 = enc_max.decrypt(&ck);
+
+    println!("Test increase_only_amendment: submitted {:?}, history = {:?}, max = {}", submitted, history, max);
+    assert_eq!(submitted, vec![200, 150, 260]);
+    assert_eq!(history, vec![200, 200, 260]);
+    assert_eq!(max, 260);
+}
+
+#[test]
+fn forbidden_amendment() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0).with_amendment_policy(AmendmentPolicy::Forbid);
+    auction.add_bid("A", 200).unwrap();
+
+    let result = auction.add_bid("A", 300);
+
+    println!("Test forbidden_amendment: second bid result = {:?}", result);
+    assert_eq!(result, Err(AuctionError::AmendmentForbidden("A".to_string())));
+    assert_eq!(auction.bid_history("A").len(), 1);
+}
+
+#[test]
+fn withdrawal_with_penalty_and_cutoff() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0)
+        .with_withdrawal_cutoff(AuctionPhase::TieBreak)
+        .with_withdrawal_penalty(25);
+    auction.add_bid("A", 200).unwrap();
+    auction.add_bid("B", 300).unwrap();
+
+    auction.withdraw_bid("B").unwrap();
+    auction.set_phase(AuctionPhase::TieBreak);
+    let late = auction.withdraw_bid("A");
+
+    println!("Test withdrawal_with_penalty_and_cutoff: late withdrawal = {:?}", late);
+    println!("{}", auction.audit_export());
+    assert_eq!(late, Err(AuctionError::WithdrawalClosed(AuctionPhase::TieBreak)));
+    assert_eq!(auction.count_bids(), 1);
+    assert_eq!(auction.withdrawals().len(), 1);
+    assert_eq!(
+        auction.ledger("B"),
+        &[LedgerEntry::WithdrawalPenalty { amount: 25, phase: AuctionPhase::Bidding }]
+    );
+    assert!(auction.audit_export().contains("withdrawal user=B phase=Bidding"));
+}
+
+#[test]
+fn withdrawal_does_not_reset_amendment_policy() {
+    let ck = setup_fhe();
+    let mut increase_only = Auction::new(&ck, 0).with_amendment_policy(AmendmentPolicy::IncreaseOnly);
+    increase_only.add_bid("A", 300).unwrap();
+    increase_only.withdraw_bid("A").unwrap();
+    increase_only.add_bid("A", 100).unwrap(); // lower re-bid is obliviously ignored
+
+    let mut forbid = Auction::new(&ck, 0).with_amendment_policy(AmendmentPolicy::Forbid);
+    forbid.add_bid("A", 300).unwrap();
+    forbid.withdraw_bid("A").unwrap();
+    let rebid = forbid.add_bid("A", 100);
+
+    let (enc_max, _) = increase_only.compute_max_encrypted_bid().unwrap();
+    let max: u16 = enc_max.decrypt(&ck);
+
+    println!("Test withdrawal_does_not_reset_amendment_policy: increase-only max = {}, forbidden re-bid = {:?}", max, rebid);
+    assert_eq!(max, 300);
+    assert_eq!(rebid, Err(AuctionError::AmendmentForbidden("A".to_string())));
+    assert_eq!(forbid.count_bids(), 0);
+}
+
+#[test]
+fn top_k_uniform_price() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0);
+    let vals = [120, 400, 250, 310, 180];
+    for (i, &v) in vals.iter().enumerate() {
+        auction.add_bid(&format!("U{}", i), v).unwrap();
+    }
+
+    let (enc_price, winners) = auction.compute_top_k(3, UniformPriceRule::KPlusOneHighest).unwrap();
+    let price: u16 = enc_price.decrypt(&ck);
+    let (enc_kth, _) = auction.compute_top_k(3, UniformPriceRule::KthHighest).unwrap();
+    let kth: u16 = enc_kth.decrypt(&ck);
+
+    println!("Test top_k_uniform_price: bids {:?}, k = 3, winners = {:?}, price = {}, kth = {}", vals, winners, price, kth);
+    assert_eq!(winners, vec!["U1".to_string(), "U2".to_string(), "U3".to_string()]);
+    assert_eq!(price, 180);
+    assert_eq!(kth, 250);
+}
+
+#[test]
+fn encrypted_ranking() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0);
+    let vals = [150, 420, 90, 300, 275];
+    for (i, &v) in vals.iter().enumerate() {
+        auction.add_bid(&format!("R{}", i), v).unwrap();
+    }
+
+    let ranking = auction.compute_ranking();
+    let ranks = auction.reveal_ranks(&ranking).unwrap();
+    let top: u16 = ranking.value_at(0).decrypt(&ck);
+
+    println!("Test encrypted_ranking: bids {:?}, ranks = {:?}, top = {}", vals, ranks, top);
+    assert_eq!(ranks, vec!["R1", "R3", "R4", "R0", "R2"]);
+    assert_eq!(top, 420);
+}
+
+#[test]
+fn encrypted_statistics() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0);
+    let vals = [100, 250, 200, 350];
+    for (i, &v) in vals.iter().enumerate() {
+        auction.add_bid(&format!("S{}", i), v).unwrap();
+    }
+
+    let stats = auction.compute_statistics(&[0, 200, 300]);
+    let median: u16 = stats.median.decrypt(&ck);
+    let min: u16 = stats.min.decrypt(&ck);
+    let range: u16 = stats.range.decrypt(&ck);
+    let sum: u32 = stats.sum.decrypt(&ck);
+    let sum_sq: u64 = stats.sum_of_squares.decrypt(&ck);
+    let histogram: Vec<u16> = stats.histogram.iter().map(|c| c.decrypt(&ck)).collect();
+    let var = statistics::variance(sum, sum_sq, stats.count);
+
+    // sorted [100, 200, 250, 350]: median floor((200 + 250) / 2) = 225
+    println!(
+        "Test encrypted_statistics: bids {:?}, median = {}, min = {}, range = {}, variance = {}, histogram = {:?}",
+        vals, median, min, range, var, histogram
+    );
+    assert_eq!(median, 225);
+    assert_eq!(min, 100);
+    assert_eq!(range, 250);
+    assert_eq!(sum_sq, 100 * 100 + 250 * 250 + 200 * 200 + 350 * 350);
+    assert!((var - 8125.0).abs() < 1e-6);
+    assert_eq!(histogram, vec![1, 2, 1]);
+}
+
+#[test]
+fn trimmed_average() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0);
+    let vals = [10, 250, 200, 350, 900];
+    for (i, &v) in vals.iter().enumerate() {
+        auction.add_bid(&format!("T{}", i), v).unwrap();
+    }
+
+    let trimmed = auction.compute_trimmed_average(1, RoundingMode::Nearest);
+    let avg: u16 = trimmed.average.decrypt(&ck);
+
+    // drop 10 and 900: (250 + 200 + 350) / 3 = 266.67 -> 267
+    println!("Test trimmed_average: bids {:?}, trimmed avg = {}", vals, avg);
+    assert_eq!(trimmed.count, 3);
+    assert_eq!(avg, 267);
+}
+
+#[test]
+fn disclosure_policy_refuses_outputs() {
+    let ck = setup_fhe();
+    let policy = DisclosurePolicy::none().allow(Disclosure::ClearingPrice);
+    let mut auction = Auction::new(&ck, 0).with_disclosure_policy(policy);
+    auction.add_bid("A", 200).unwrap();
+    auction.add_bid("B", 300).unwrap();
+
+    let winners = auction.compute_max_encrypted_bid().map(|(_, winners)| winners);
+    let max = auction.reveal_highest_bid("public");
+    let avg = auction.reveal_average("public", RoundingMode::Floor);
+
+    println!("Test disclosure_policy_refuses_outputs: max = {:?}, avg = {:?}, winners = {:?}", max, avg, winners);
+    println!("{}", auction.public_terms());
+    assert_eq!(max, Ok(300));
+    assert_eq!(avg, Err(AuctionError::NotDisclosed(Disclosure::Average)));
+    assert_eq!(winners, Err(AuctionError::NotDisclosed(Disclosure::WinnerIdentity)));
+    assert_eq!(auction.reveal_bid_count(), Err(AuctionError::NotDisclosed(Disclosure::BidCount)));
+    assert!(auction.public_terms().contains("Disclosed outputs: clearing price"));
+}
+
+#[test]
+fn noisy_average_respects_budget() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0).with_privacy_budget(1.0);
+    auction.add_bid("A", 2).unwrap();
+    auction.add_bid("B", 3).unwrap();
+
+    // A cap of 4 over 2 bids gives sensitivity 2, which keeps the number of
+    // encrypted noise trials low (37 per sample).
+    let noisy = auction.reveal_noisy_average(4, 0.75).unwrap();
+    let refused = auction.reveal_noisy_average(4, 0.5);
+
+    println!("Test noisy_average_respects_budget: noisy avg = {}, second attempt = {:?}", noisy, refused);
+    assert_eq!(refused, Err(AuctionError::PrivacyBudgetExhausted));
+    assert!((auction.privacy_budget().unwrap().spent() - 0.75).abs() < 1e-9);
+    assert!((noisy - 2).abs() <= 37);
+}
+
+#[test]
+fn noisy_average_refuses_uncalibrated_noise() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0).with_privacy_budget(1.0);
+    auction.add_bid("A", 200).unwrap();
+    auction.add_bid("B", 300).unwrap();
+
+    // Uncapped 16-bit bids: one of 2 bids moves the average by up to 32768.
+    let refused = auction.reveal_noisy_average(u16::MAX, 1.0);
+
+    println!("Test noisy_average_refuses_uncalibrated_noise: result = {:?}", refused);
+    assert!(matches!(refused, Err(AuctionError::NoiseTooLarge(trials)) if trials > 512));
+    assert_eq!(auction.privacy_budget().unwrap().spent(), 0.0);
+    assert!(auction.leakage_log().records().is_empty());
+}
+
+#[test]
+fn noisy_bid_count() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0).with_privacy_budget(1.0);
+    auction.add_bid("A", 200).unwrap();
+    auction.add_bid("B", 300).unwrap();
+    auction.add_bid("C", 250).unwrap();
+
+    let noisy = auction.reveal_noisy_bid_count(0.75).unwrap();
+
+    println!("Test noisy_bid_count: 3 bids, noisy count = {}", noisy);
+    assert!((noisy - 3).abs() <= 19);
+    assert_eq!(auction.leakage_log().records()[0].output, Disclosure::BidCount);
+}
+
+#[test]
+fn leakage_report_lists_decryptions() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0);
+    auction.add_bid("A", 200).unwrap();
+    auction.add_bid("B", 300).unwrap();
+
+    let (_, winners) = auction.compute_max_encrypted_bid().unwrap();
+    let max = auction.reveal_highest_bid("public").unwrap();
+
+    let log = auction.leakage_log();
+    println!("Test leakage_report_lists_decryptions: winners = {:?}, max = {}", winners, max);
+    println!("{}", log.to_text());
+    // Two equality bits for the auctioneer, one price for the public.
+    assert_eq!(log.records().len(), 3);
+    assert!(log.to_text().contains("public learned:"));
+    assert!(log.to_json().contains("{\"output\":\"clearing price\",\"item\":\"highest bid\",\"value\":\"300\"}"));
+}
+
+#[test]
+fn proxy_bidding_outcome() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0);
+    auction.add_bid("A", 500).unwrap();
+    auction.add_bid("B", 320).unwrap();
+    auction.add_bid("C", 495).unwrap();
+
+    let (enc_price, winner) = auction.compute_proxy_outcome(10).unwrap();
+    let price: u16 = enc_price.decrypt(&ck);
+
+    // second-highest 495 + 10 = 505, capped at the winner's maximum of 500
+    println!("Test proxy_bidding_outcome: maxima [500,320,495], increment 10, winner = {:?}, price = {}", winner, price);
+    assert_eq!(winner, vec!["A".to_string()]);
+    assert_eq!(price, 500);
+}
+
+#[test]
+fn dutch_clock_acceptance() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0);
+    auction.add_bid("A", 180).unwrap();
+    auction.add_bid("B", 260).unwrap();
+    auction.add_bid("C", 90).unwrap();
+
+    let outcome = auction.run_dutch_clock(&[400, 300, 250, 200]).unwrap().unwrap();
+
+    println!("Test dutch_clock_acceptance: thresholds [180,260,90], outcome = {:?}", outcome);
+    assert_eq!(outcome.price, 250);
+    assert_eq!(outcome.winner, Some("B".to_string()));
+    assert_eq!(outcome.steps, 3);
+    assert_eq!(auction.run_dutch_clock(&[500, 450]).unwrap(), None);
+}
+
+#[test]
+fn japanese_clock_last_bidder_standing() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0);
+    auction.add_bid("A", 230).unwrap();
+    auction.add_bid("B", 310).unwrap();
+    auction.add_bid("C", 150).unwrap();
+
+    let outcome = auction.run_japanese_clock(&[100, 200, 250, 300, 350]).unwrap().unwrap();
+
+    // at 250 only B (drop-out 310) is still in
+    println!("Test japanese_clock_last_bidder_standing: drop-outs [230,310,150], outcome = {:?}", outcome);
+    assert_eq!(outcome.price, 250);
+    assert_eq!(outcome.winner, Some("B".to_string()));
+    assert_eq!(outcome.steps, 3);
+}
+
+#[test]
+fn gsp_slot_payments() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 5);
+    auction.add_bid("A", 40).unwrap();
+    auction.add_bid("B", 90).unwrap();
+    auction.add_bid("C", 65).unwrap();
+
+    let slots = auction.compute_gsp(2).unwrap();
+    let assigned: Vec<(Option<String>, u16)> = slots.iter().map(|s| (s.bidder.clone(), s.payment)).collect();
+
+    println!("Test gsp_slot_payments: bids [40,90,65], 2 slots, assignments = {:?}", assigned);
+    assert_eq!(
+        assigned,
+        vec![(Some("B".to_string()), 65), (Some("C".to_string()), 40)]
+    );
+}
+
+#[test]
+fn all_pay_revenue_and_payments() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0);
+    auction.add_bid("A", 120).unwrap();
+    auction.add_bid("B", 300).unwrap();
+    auction.add_bid("C", 80).unwrap();
+    // Missing keys are reported for the first bidder in order, before anything is decrypted.
+    assert_eq!(auction.compute_all_pay().err(), Some(AuctionError::NotRegistered("A".to_string())));
+    assert!(auction.leakage_log().records().is_empty());
+
+    // Each bidder holds their own key pair; the auction only gets the public key.
+    let mut bidder_keys = Vec::new();
+    for user in ["A", "B", "C"] {
+        let (bidder_ck, _) = generate_keys(ConfigBuilder::default().build());
+        auction.register_bidder_key(user, PublicKey::new(&bidder_ck));
+        bidder_keys.push(bidder_ck);
+    }
+
+    let outcome = auction.compute_all_pay().unwrap();
+    let revenue: u32 = outcome.revenue.decrypt(&ck);
+    let payment_c: u16 = outcome.payments["C"].decrypt(&bidder_keys[2]);
+
+    println!("Test all_pay_revenue_and_payments: winners = {:?}, revenue = {}, C pays {}", outcome.winners, revenue, payment_c);
+    assert_eq!(outcome.winners, vec!["B".to_string()]);
+    assert_eq!(revenue, 500);
+    assert_eq!(outcome.payments.len(), 3);
+    assert_eq!(payment_c, 80);
+    // The auctioneer is logged as having seen each payment, without the value.
+    let log = auction.leakage_log();
+    let record = log.records().iter().find(|r| r.item == "C's all-pay payment").unwrap();
+    assert_eq!(record.party, AUCTIONEER);
+    assert!(!record.value.contains("80"));
+}
+
+#[test]
+fn buy_it_now_closes_auction() {
+    let ck = setup_fhe();
+    let sealed_price = tfhe::FheUint16::encrypt(500u16, &ck);
+    let mut auction = Auction::new(&ck, 0).with_buy_it_now(BuyItNow::Encrypted(sealed_price));
+    auction.add_bid("A", 200).unwrap();
+    assert_eq!(auction.phase(), AuctionPhase::Bidding);
+    auction.add_bid("B", 520).unwrap();
+
+    let late = auction.add_bid("C", 600);
+    let buyer = auction.buy_it_now_buyer().unwrap();
+
+    println!("Test buy_it_now_closes_auction: buyer = {:?}, late bid = {:?}", buyer, late);
+    assert_eq!(auction.phase(), AuctionPhase::Closed);
+    assert_eq!(late, Err(AuctionError::AuctionClosed));
+    assert_eq!(buyer, Some("B".to_string()));
+}
+
+#[test]
+fn candle_discards_late_bids() {
+    let ck = setup_fhe();
+    let clock = MockClock::new(0);
+    let mut auction = Auction::new(&ck, 0).with_clock(Box::new(clock.clone()));
+    clock.set(100);
+    auction.add_bid_at("A", 300, 0).unwrap();
+    auction.add_bid_at("B", 250, 0).unwrap();
+    // Submitted at the very end of the window, always after the hidden cutoff.
+    auction.add_bid_at("B", 900, 100).unwrap();
+    // A replayed bid may not be backdated before B's latest one.
+    assert_eq!(auction.add_bid_at("B", 50, 10), Err(AuctionError::OutOfOrder("B".to_string())));
+
+    let (enc_max, winners) = auction.compute_candle_max(100).unwrap();
+    let max: u16 = enc_max.decrypt(&ck);
+
+    println!("Test candle_discards_late_bids: A 300@0, B 250@0, B 900@100, max = {}, winners = {:?}", max, winners);
+    assert_eq!(max, 300);
+    assert_eq!(winners, vec!["A".to_string()]);
+}
+
+#[test]
+fn soft_close_extends_deadline() {
+    let ck = setup_fhe();
+    let clock = MockClock::new(1_000);
+    let mut auction = Auction::new(&ck, 0)
+        .with_clock(Box::new(clock.clone()))
+        .with_soft_close(SoftClose {
+            duration_secs: 600,
+            extension_window_secs: 60,
+            extension_secs: 120,
+            max_extensions: 1,
+        });
+
+    clock.advance(100);
+    auction.add_bid("A", 200).unwrap();   // early: no extension
+    clock.advance(470);
+    auction.add_bid("B", 250).unwrap();   // at 570, within 60s of 600: extended to 720
+    clock.advance(100);
+    auction.add_bid("A", 300).unwrap();   // at 670, within window but no extensions left
+    clock.advance(60);
+    let late = auction.add_bid("B", 350); // at 730, after the deadline
+
+    println!("Test soft_close_extends_deadline: deadline = {:?}, late bid = {:?}", auction.deadline(), late);
+    assert_eq!(auction.deadline(), Some(720));
+    assert_eq!(late, Err(AuctionError::AuctionClosed));
+    assert!(auction.close_if_expired());
+    assert_eq!(auction.bid_history("B")[0].at, 570);
+}
+
+#[test]
+fn replayed_bids_cannot_bypass_the_deadline() {
+    let ck = setup_fhe();
+    let clock = MockClock::new(0);
+    let mut auction = Auction::new(&ck, 0)
+        .with_clock(Box::new(clock.clone()))
+        .with_soft_close(SoftClose {
+            duration_secs: 600,
+            extension_window_secs: 60,
+            extension_secs: 120,
+            max_extensions: 1,
+        });
+
+    clock.set(300);
+    let future = auction.add_bid_at("A", 200, 400);
+    clock.set(650);
+    // Backdated into the extension window, but the clock is past the deadline.
+    let backdated = auction.add_bid_at("A", 200, 580);
+
+    println!("Test replayed_bids_cannot_bypass_the_deadline: future = {:?}, backdated = {:?}", future, backdated);
+    assert_eq!(future, Err(AuctionError::FutureTimestamp("A".to_string())));
+    assert_eq!(backdated, Err(AuctionError::AuctionClosed));
+    assert_eq!(auction.deadline(), Some(600));
+    assert_eq!(auction.phase(), AuctionPhase::Closed);
+}
+
+#[test]
+fn raffle_draws_a_ticket_holder() {
+    let ck = setup_fhe();
+    let mut raffle = Raffle::new(&ck);
+    raffle.buy_tickets("A", 3).unwrap();
+    raffle.buy_tickets("B", 0).unwrap();
+    raffle.buy_tickets("C", 5).unwrap();
+
+    let winner = raffle.draw_winner().unwrap();
+
+    println!("Test raffle_draws_a_ticket_holder: tickets [A: 3, B: 0, C: 5], winner = {:?}", winner);
+    // B holds no tickets and can never win.
+    assert!(winner == Some("A".to_string()) || winner == Some("C".to_string()));
+}
+
+#[test]
+fn raffle_single_holder_always_wins() {
+    let ck = setup_fhe();
+    let mut raffle = Raffle::new(&ck);
+    raffle.buy_tickets("A", 0).unwrap();
+    raffle.buy_tickets("B", 4).unwrap();
+    raffle.buy_tickets("C", 0).unwrap();
+
+    let winner = raffle.draw_winner().unwrap();
+
+    println!("Test raffle_single_holder_always_wins: tickets [A: 0, B: 4, C: 0], winner = {:?}", winner);
+    assert_eq!(winner, Some("B".to_string()));
+    assert_eq!(raffle.tickets().leakage_log().records().len(), 1);
+}
+
+#[test]
+fn poll_rejects_unregistered_voters() {
+    let ck = setup_fhe();
+    let mut poll = Poll::new(&ck, &["Yes", "No"]);
+    assert!(poll.register_voter("A"));
+    assert!(!poll.register_voter("A"));
+
+    poll.cast_vote("A", 0).unwrap();
+    let result = poll.cast_vote("B", 1);
+
+    println!("Test poll_rejects_unregistered_voters: B unregistered -> {:?}", result);
+    assert_eq!(result, Err(AuctionError::NotRegistered("B".to_string())));
+    assert_eq!(poll.count_ballots(), 1);
+
+    poll.close();
+    assert_eq!(poll.cast_vote("A", 1), Err(AuctionError::AuctionClosed));
+}
+
+#[test]
+fn consortium_bid_saturates_and_requires_a_win() {
+    let ck = setup_fhe();
+    let mut auction = Auction::new(&ck, 0);
+    auction.register_consortium("Big", &["X", "Y"]).unwrap();
+    auction.register_consortium("Small", &["Z"]).unwrap();
+    auction.add_consortium_share("Big", "X", 40000).unwrap();
+    auction.add_consortium_share("Big", "Y", 30000).unwrap();
+    auction.add_consortium_share("Small", "Z", 100).unwrap();
+    auction.submit_consortium_bid("Big").unwrap();
+    auction.submit_consortium_bid("Small").unwrap();
+    auction.add_bid("Solo", 60000).unwrap();
+    let (member_ck, _) = generate_keys(ConfigBuilder::default().build());
+    auction.register_bidder_key("X", PublicKey::new(&member_ck));
+    auction.register_bidder_key("Z", PublicKey::new(&member_ck));
+
+    let (enc_max, winners) = auction.compute_max_encrypted_bid().unwrap();
+    let max: u16 = enc_max.decrypt(&ck);
+    let loser = auction.reveal_contribution("Small", "Z").err();
+
+    println!("Test consortium_bid_saturates_and_requires_a_win: max = {}, winners = {:?}, Small/Z = {:?}", max, winners, loser);
+    // 40000 + 30000 saturates at 65535 instead of wrapping to 4464.
+    assert_eq!(max, u16::MAX);
+    assert_eq!(winners, vec!["Big".to_string()]);
+    assert_eq!(loser, Some(AuctionError::NotWinner("Small".to_string())));
+    assert_eq!(auction.reveal_contribution("Big", "Y").err(), Some(AuctionError::NotRegistered("Y".to_string())));
+    let share_x: u16 = auction.reveal_contribution("Big", "X").unwrap().decrypt(&member_ck);
+    assert_eq!(share_x, 40000);
+    assert_eq!(auction.bid_history("Big").len(), 1);
+}